@@ -40,6 +40,7 @@ fn main() {
                 matches.get_one::<String>("category").map(String::as_str),
                 matches.get_one::<String>("type").map(String::as_str),
                 matches.get_flag("msrv"),
+                matches.get_one::<String>("seed"),
             ) {
                 Ok(()) => update_lints::update(update_lints::UpdateMode::Change),
                 Err(e) => eprintln!("Unable to create lint: {e}"),
@@ -206,6 +207,11 @@ fn get_clap_config() -> ArgMatches {
                         .long("msrv")
                         .action(ArgAction::SetTrue)
                         .help("Add MSRV config code to the lint"),
+                    Arg::new("seed")
+                        .long("seed")
+                        .value_name("path")
+                        .conflicts_with_all(["type", "msrv"])
+                        .help("Use the lint and test printed by `#[clippy::author = \"<name>\"]` in the given file"),
                 ]),
             Command::new("setup")
                 .about("Support for setting up your personal development environment")
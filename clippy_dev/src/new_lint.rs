@@ -13,6 +13,13 @@ struct LintData<'a> {
     category: &'a str,
     ty: Option<&'a str>,
     project_root: PathBuf,
+    seed: Option<Seed>,
+}
+
+/// The lint module and test printed by `#[clippy::author = "lint_name"]`.
+struct Seed {
+    lint: String,
+    test: String,
 }
 
 trait Context {
@@ -43,18 +50,33 @@ pub fn create(
     category: Option<&str>,
     mut ty: Option<&str>,
     msrv: bool,
+    seed: Option<&String>,
 ) -> io::Result<()> {
     if category == Some("cargo") && ty.is_none() {
         // `cargo` is a special category, these lints should always be in `clippy_lints/src/cargo`
         ty = Some("cargo");
     }
 
+    let name = lint_name.expect("`name` argument is validated by clap");
+    let category = category.expect("`category` argument is validated by clap");
+    let seed = match seed {
+        Some(_) if pass == "early" || ty.is_some() => {
+            return Err(io::Error::new(
+                ErrorKind::Other,
+                "a seed can only be used for late pass lints in `clippy_lints/src`",
+            ));
+        },
+        Some(path) => Some(read_seed(Path::new(path), name, category).context("Unable to read the seed")?),
+        None => None,
+    };
+
     let lint = LintData {
         pass,
-        name: lint_name.expect("`name` argument is validated by clap"),
-        category: category.expect("`category` argument is validated by clap"),
+        name,
+        category,
         ty,
         project_root: clippy_project_root(),
+        seed,
     };
 
     create_lint(&lint, msrv).context("Unable to create lint implementation")?;
@@ -79,7 +101,10 @@ fn create_lint(lint: &LintData<'_>, enable_msrv: bool) -> io::Result<()> {
     if let Some(ty) = lint.ty {
         create_lint_for_ty(lint, enable_msrv, ty)
     } else {
-        let lint_contents = get_lint_file_contents(lint, enable_msrv);
+        let lint_contents = match &lint.seed {
+            Some(seed) => seed.lint.clone(),
+            None => get_lint_file_contents(lint, enable_msrv),
+        };
         let lint_path = format!("clippy_lints/src/{}.rs", lint.name);
         write_file(lint.project_root.join(&lint_path), lint_contents.as_bytes())?;
         println!("Generated lint file: `{lint_path}`");
@@ -112,7 +137,10 @@ fn create_test(lint: &LintData<'_>) -> io::Result<()> {
         println!("Generated test directories: `{relative_test_dir}/pass`, `{relative_test_dir}/fail`");
     } else {
         let test_path = format!("tests/ui/{}.rs", lint.name);
-        let test_contents = get_test_file_contents(lint.name);
+        let test_contents = match &lint.seed {
+            Some(seed) => seed.test.clone(),
+            None => get_test_file_contents(lint.name),
+        };
         write_file(lint.project_root.join(&test_path), test_contents)?;
 
         println!("Generated test file: `{test_path}`");
@@ -162,6 +190,39 @@ fn write_file<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Resul
     inner(path.as_ref(), contents.as_ref()).context(format!("writing to file: {}", path.as_ref().display()))
 }
 
+/// Reads the output of `#[clippy::author = "lint_name"]` from `path`, using `category` for the
+/// lint instead of the default one.
+fn read_seed(path: &Path, lint_name: &str, category: &str) -> io::Result<Seed> {
+    let contents = fs::read_to_string(path)?;
+    let lint_header = format!("// clippy_lints/src/{lint_name}.rs\n");
+    let test_header = format!("// tests/ui/{lint_name}.rs\n");
+    let (Some(lint_start), Some(test_start)) = (contents.find(&lint_header), contents.find(&test_header)) else {
+        return Err(io::Error::new(
+            ErrorKind::Other,
+            format!("no output of `#[clippy::author = \"{lint_name}\"]` found"),
+        ));
+    };
+
+    let name_upper = lint_name.to_uppercase();
+    let lint = contents[lint_start + lint_header.len()..test_start]
+        .trim_end()
+        .replacen(
+            &format!("pub {name_upper},\n    nursery,"),
+            &format!("pub {name_upper},\n    {category},"),
+            1,
+        )
+        + "\n";
+    let test = &contents[test_start + test_header.len()..];
+    let test = test
+        .split("\n// clippy_lints/src/")
+        .next()
+        .unwrap_or(test)
+        .trim_end()
+        .to_string()
+        + "\n";
+    Ok(Seed { lint, test })
+}
+
 fn to_camel_case(name: &str) -> String {
     name.split('_')
         .map(|s| {
@@ -0,0 +1,51 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+fn main() {
+    // Record the commit of the compiler, checked by the driver before loading a plugin
+    let version = env::var("RUSTC")
+        .ok()
+        .and_then(|rustc| Command::new(rustc).arg("-vV").output().ok())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    let commit_hash = version
+        .lines()
+        .find_map(|line| line.strip_prefix("commit-hash: "))
+        .unwrap_or("unknown");
+    println!("cargo:rustc-env=RUSTC_COMMIT_HASH={commit_hash}");
+
+    // Record a hash of the sources of `clippy_lints` and `clippy_utils`, plugins built against other
+    // sources are rejected by the driver
+    let mut hasher = DefaultHasher::new();
+    for dir in ["src", "../clippy_utils/src", "../declare_clippy_lint/src"] {
+        let dir = Path::new(dir);
+        if dir.is_dir() {
+            println!("cargo:rerun-if-changed={}", dir.display());
+            hash_dir(dir, &mut hasher);
+        }
+    }
+    println!("cargo:rustc-env=CLIPPY_LINTS_FINGERPRINT={:016x}", hasher.finish());
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
+
+fn hash_dir(dir: &Path, hasher: &mut DefaultHasher) {
+    let mut paths = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            hash_dir(&path, hasher);
+        } else if let Ok(contents) = fs::read(&path) {
+            path.file_name().hash(hasher);
+            contents.hash(hasher);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! checks for attributes
 
+use crate::cargo;
 use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_sugg, span_lint_and_then};
 use clippy_utils::is_from_proc_macro;
 use clippy_utils::macros::{is_panic, macro_backtrace};
@@ -779,6 +780,10 @@ impl_lint_pass!(EarlyAttributes => [
 ]);
 
 impl EarlyLintPass for EarlyAttributes {
+    fn check_crate(&mut self, cx: &EarlyContext<'_>, _: &rustc_ast::Crate) {
+        cargo::collect_crate_attr_features(cx);
+    }
+
     fn check_item(&mut self, cx: &EarlyContext<'_>, item: &rustc_ast::Item) {
         check_empty_line_after_outer_attr(cx, item);
     }
@@ -788,6 +793,13 @@ impl EarlyLintPass for EarlyAttributes {
         check_mismatched_target_os(cx, attr);
         check_minimal_cfg_condition(cx, attr);
         check_misused_cfg(cx, attr);
+        // For the cargo feature and dependency lints, as the items with a false `cfg` are removed by
+        // expansion
+        cargo::collect_attr_features(attr);
+    }
+
+    fn check_mac(&mut self, _: &EarlyContext<'_>, mac: &rustc_ast::MacCall) {
+        cargo::collect_macro_features(mac);
     }
 
     extract_msrv_attr!(EarlyContext);
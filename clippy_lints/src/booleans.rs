@@ -1,4 +1,4 @@
-use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_hir_and_then};
+use clippy_utils::diagnostics::span_lint_hir_and_then;
 use clippy_utils::eq_expr_value;
 use clippy_utils::source::snippet_opt;
 use clippy_utils::ty::{implements_trait, is_type_diagnostic_item};
@@ -7,7 +7,7 @@ use rustc_ast::ast::LitKind;
 use rustc_errors::Applicability;
 use rustc_hir::intravisit::{walk_expr, FnKind, Visitor};
 use rustc_hir::{BinOpKind, Body, Expr, ExprKind, FnDecl, UnOp};
-use rustc_lint::{LateContext, LateLintPass, Level};
+use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::{declare_lint_pass, declare_tool_lint};
 use rustc_span::def_id::LocalDefId;
 use rustc_span::source_map::Span;
@@ -429,25 +429,23 @@ impl<'a, 'tcx> NonminimalBoolVisitor<'a, 'tcx> {
                 }
             }
             let nonminimal_bool_lint = |suggestions: Vec<_>| {
-                if self.cx.tcx.lint_level_at_node(NONMINIMAL_BOOL, e.hir_id).0 != Level::Allow {
-                    span_lint_hir_and_then(
-                        self.cx,
-                        NONMINIMAL_BOOL,
-                        e.hir_id,
-                        e.span,
-                        "this boolean expression can be simplified",
-                        |diag| {
-                            diag.span_suggestions(
-                                e.span,
-                                "try",
-                                suggestions,
-                                // nonminimal_bool can produce minimal but
-                                // not human readable expressions (#3141)
-                                Applicability::Unspecified,
-                            );
-                        },
-                    );
-                }
+                span_lint_hir_and_then(
+                    self.cx,
+                    NONMINIMAL_BOOL,
+                    e.hir_id,
+                    e.span,
+                    "this boolean expression can be simplified",
+                    |diag| {
+                        diag.span_suggestions(
+                            e.span,
+                            "try",
+                            suggestions,
+                            // nonminimal_bool can produce minimal but
+                            // not human readable expressions (#3141)
+                            Applicability::Unspecified,
+                        );
+                    },
+                );
             };
             if improvements.is_empty() {
                 let mut visitor = NotSimplificationVisitor { cx: self.cx };
@@ -503,16 +501,16 @@ impl<'a, 'tcx> Visitor<'tcx> for NotSimplificationVisitor<'a, 'tcx> {
         if let ExprKind::Unary(UnOp::Not, inner) = &expr.kind &&
             !inner.span.from_expansion() &&
             let Some(suggestion) = simplify_not(self.cx, inner)
-			&& self.cx.tcx.lint_level_at_node(NONMINIMAL_BOOL, expr.hir_id).0 != Level::Allow
         {
-            span_lint_and_sugg(
+            span_lint_hir_and_then(
                 self.cx,
                 NONMINIMAL_BOOL,
+                expr.hir_id,
                 expr.span,
                 "this boolean expression can be simplified",
-                "try",
-                suggestion,
-                Applicability::MachineApplicable,
+                |diag| {
+                    diag.span_suggestion(expr.span, "try", suggestion, Applicability::MachineApplicable);
+                },
             );
         }
 
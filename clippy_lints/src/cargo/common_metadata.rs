@@ -4,7 +4,9 @@ use cargo_metadata::Metadata;
 use clippy_utils::diagnostics::span_lint;
 use rustc_lint::LateContext;
 use rustc_span::source_map::DUMMY_SP;
+use rustc_span::Span;
 
+use super::manifest::Manifest;
 use super::CARGO_COMMON_METADATA;
 
 pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata, ignore_publish: bool) {
@@ -12,36 +14,40 @@ pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata, ignore_publish: b
         // only run the lint if publish is `None` (`publish = true` or skipped entirely)
         // or if the vector isn't empty (`publish = ["something"]`)
         if package.publish.as_ref().filter(|publish| publish.is_empty()).is_none() || ignore_publish {
+            let span = Manifest::load(cx, package.manifest_path.as_std_path())
+                .and_then(|manifest| manifest.package_span())
+                .unwrap_or(DUMMY_SP);
+
             if is_empty_str(&package.description) {
-                missing_warning(cx, package, "package.description");
+                missing_warning(cx, package, span, "package.description");
             }
 
             if is_empty_str(&package.license) && is_empty_str(&package.license_file) {
-                missing_warning(cx, package, "either package.license or package.license_file");
+                missing_warning(cx, package, span, "either package.license or package.license_file");
             }
 
             if is_empty_str(&package.repository) {
-                missing_warning(cx, package, "package.repository");
+                missing_warning(cx, package, span, "package.repository");
             }
 
             if is_empty_str(&package.readme) {
-                missing_warning(cx, package, "package.readme");
+                missing_warning(cx, package, span, "package.readme");
             }
 
             if is_empty_vec(&package.keywords) {
-                missing_warning(cx, package, "package.keywords");
+                missing_warning(cx, package, span, "package.keywords");
             }
 
             if is_empty_vec(&package.categories) {
-                missing_warning(cx, package, "package.categories");
+                missing_warning(cx, package, span, "package.categories");
             }
         }
     }
 }
 
-fn missing_warning(cx: &LateContext<'_>, package: &cargo_metadata::Package, field: &str) {
+fn missing_warning(cx: &LateContext<'_>, package: &cargo_metadata::Package, span: Span, field: &str) {
     let message = format!("package `{}` is missing `{field}` metadata", package.name);
-    span_lint(cx, CARGO_COMMON_METADATA, DUMMY_SP, &message);
+    span_lint(cx, CARGO_COMMON_METADATA, span, &message);
 }
 
 fn is_empty_str<T: AsRef<std::ffi::OsStr>>(value: &Option<T>) -> bool {
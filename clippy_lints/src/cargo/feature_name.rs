@@ -1,8 +1,12 @@
 use cargo_metadata::Metadata;
-use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_sugg};
+use clippy_utils::source::snippet_opt;
+use rustc_errors::Applicability;
 use rustc_lint::LateContext;
 use rustc_span::source_map::DUMMY_SP;
+use rustc_span::Span;
 
+use super::manifest::Manifest;
 use super::{NEGATIVE_FEATURE_NAMES, REDUNDANT_FEATURE_NAMES};
 
 static PREFIXES: [&str; 8] = ["no-", "no_", "not-", "not_", "use-", "use_", "with-", "with_"];
@@ -10,6 +14,7 @@ static SUFFIXES: [&str; 2] = ["-support", "_support"];
 
 pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
     for package in &metadata.packages {
+        let manifest = Manifest::load(cx, package.manifest_path.as_std_path());
         let mut features: Vec<&String> = package.features.keys().collect();
         features.sort();
         for feature in features {
@@ -21,8 +26,12 @@ pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
                     None
                 }
             };
+            let span = manifest
+                .as_ref()
+                .and_then(|manifest| manifest.feature_span(feature))
+                .unwrap_or(DUMMY_SP);
             if let Some(prefix) = prefix_opt {
-                lint(cx, feature, prefix, true);
+                lint(cx, span, feature, prefix, true);
             }
 
             let suffix_opt: Option<&str> = {
@@ -36,7 +45,7 @@ pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
                 }
             };
             if let Some(suffix) = suffix_opt {
-                lint(cx, feature, suffix, false);
+                lint(cx, span, feature, suffix, false);
             }
         }
     }
@@ -46,37 +55,56 @@ fn is_negative_prefix(s: &str) -> bool {
     s.starts_with("no")
 }
 
-fn lint(cx: &LateContext<'_>, feature: &str, substring: &str, is_prefix: bool) {
+fn lint(cx: &LateContext<'_>, span: Span, feature: &str, substring: &str, is_prefix: bool) {
     let is_negative = is_prefix && is_negative_prefix(substring);
-    span_lint_and_help(
-        cx,
-        if is_negative {
-            NEGATIVE_FEATURE_NAMES
-        } else {
-            REDUNDANT_FEATURE_NAMES
-        },
-        DUMMY_SP,
-        &format!(
-            "the \"{substring}\" {} in the feature name \"{feature}\" is {}",
-            if is_prefix { "prefix" } else { "suffix" },
-            if is_negative { "negative" } else { "redundant" }
-        ),
-        None,
-        &format!(
-            "consider renaming the feature to \"{}\"{}",
-            if is_prefix {
-                feature.strip_prefix(substring)
-            } else {
-                feature.strip_suffix(substring)
-            }
-            .unwrap(),
-            if is_negative {
-                ", but make sure the feature adds functionality"
-            } else {
-                ""
-            }
-        ),
+    let msg = format!(
+        "the \"{substring}\" {} in the feature name \"{feature}\" is {}",
+        if is_prefix { "prefix" } else { "suffix" },
+        if is_negative { "negative" } else { "redundant" }
     );
+    let renamed = if is_prefix {
+        feature.strip_prefix(substring)
+    } else {
+        feature.strip_suffix(substring)
+    }
+    .unwrap();
+
+    if is_negative {
+        span_lint_and_help(
+            cx,
+            NEGATIVE_FEATURE_NAMES,
+            span,
+            &msg,
+            None,
+            &format!("consider renaming the feature to \"{renamed}\", but make sure the feature adds functionality"),
+        );
+    } else if let Some(key) = snippet_opt(cx, span) {
+        // Keep the quotes of quoted keys. The uses of the feature in the code and by dependent
+        // crates need to be renamed as well
+        let sugg = if key.starts_with('"') {
+            format!("\"{renamed}\"")
+        } else {
+            renamed.to_string()
+        };
+        span_lint_and_sugg(
+            cx,
+            REDUNDANT_FEATURE_NAMES,
+            span,
+            &msg,
+            "consider renaming the feature",
+            sugg,
+            Applicability::MaybeIncorrect,
+        );
+    } else {
+        span_lint_and_help(
+            cx,
+            REDUNDANT_FEATURE_NAMES,
+            span,
+            &msg,
+            None,
+            &format!("consider renaming the feature to \"{renamed}\""),
+        );
+    }
 }
 
 #[test]
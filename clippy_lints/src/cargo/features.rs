@@ -0,0 +1,287 @@
+//! Lints on the features of a package and their use in `cfg`s.
+//!
+//! The items whose `cfg` is false are removed during expansion, so the features used in `cfg`s are
+//! collected by the pre-expansion `EarlyAttributes` pass.
+
+use cargo_metadata::{Metadata, Package};
+use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_then};
+use clippy_utils::source::snippet_opt;
+use rustc_ast::token::{self, TokenKind};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast::{AttrStyle, Attribute, LitKind, MacCall, MetaItemKind, NestedMetaItem};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_errors::Applicability;
+use rustc_hir::CRATE_HIR_ID;
+use rustc_lint::{EarlyContext, LateContext, LintContext};
+use rustc_span::edit_distance::find_best_match_for_name;
+use rustc_span::source_map::DUMMY_SP;
+use rustc_span::{sym, Span, Symbol};
+use std::cell::RefCell;
+
+use super::manifest::Manifest;
+use super::{
+    current_package, is_only_target, IMPLICIT_FEATURES, STD_IN_DEFAULT_FEATURES, UNDECLARED_FEATURES, UNUSED_FEATURES,
+};
+
+thread_local! {
+    /// The features used in `cfg`s, by the span of their string literal.
+    static CFG_FEATURES: RefCell<FxHashMap<Span, Symbol>> = RefCell::default();
+}
+
+/// Records the features used in a `#[cfg]` or `#[cfg_attr]` attribute.
+pub(crate) fn collect_attr_features(attr: &Attribute) {
+    if (attr.has_name(sym::cfg) || attr.has_name(sym::cfg_attr))
+        && let Some(items) = attr.meta_item_list()
+    {
+        CFG_FEATURES.with(|features| collect_nested(&items, &mut features.borrow_mut()));
+    }
+}
+
+/// Records the features used in the `#![cfg_attr]`s of the crate root. They are expanded before
+/// the pre-expansion passes run, so the attributes are parsed again.
+pub(crate) fn collect_crate_attr_features(cx: &EarlyContext<'_>) {
+    if let Some(path) = cx.sess().io.input.opt_path() {
+        match rustc_parse::parse_crate_attrs_from_file(path, &cx.sess().parse_sess) {
+            Ok(attrs) => attrs.iter().for_each(collect_attr_features),
+            Err(e) => e.cancel(),
+        }
+    }
+}
+
+/// Records the features used in `cfg!` and `cfg_if!` invocations.
+pub(crate) fn collect_macro_features(mac: &MacCall) {
+    if let [.., segment] = &*mac.path.segments
+        && (segment.ident.name == sym::cfg || segment.ident.name.as_str() == "cfg_if")
+    {
+        CFG_FEATURES.with(|features| collect_tokens(&mac.args.tokens, &mut features.borrow_mut()));
+    }
+}
+
+fn collect_nested(items: &[NestedMetaItem], features: &mut FxHashMap<Span, Symbol>) {
+    for item in items {
+        let Some(meta) = item.meta_item() else {
+            continue;
+        };
+        match &meta.kind {
+            MetaItemKind::NameValue(lit) if meta.has_name(sym::feature) => {
+                if let LitKind::Str(feature, _) = lit.kind {
+                    features.insert(lit.span, feature);
+                }
+            },
+            MetaItemKind::List(items) => collect_nested(items, features),
+            _ => {},
+        }
+    }
+}
+
+fn collect_tokens(tokens: &TokenStream, features: &mut FxHashMap<Span, Symbol>) {
+    let trees: Vec<&TokenTree> = tokens.trees().collect();
+    for window in trees.windows(3) {
+        if let [
+            TokenTree::Token(name, _),
+            TokenTree::Token(eq, _),
+            TokenTree::Token(lit_token, _),
+        ] = window
+            && let TokenKind::Ident(sym::feature, false) = name.kind
+            && let TokenKind::Eq = eq.kind
+            && let TokenKind::Literal(lit) = lit_token.kind
+            && let token::LitKind::Str = lit.kind
+        {
+            features.insert(lit_token.span, lit.symbol);
+        }
+    }
+    for tree in trees {
+        if let TokenTree::Delimited(_, _, tokens) = tree {
+            collect_tokens(tokens, features);
+        }
+    }
+}
+
+/// Returns the features used in the `cfg`s of the crate, with the span of their string literal,
+/// sorted by span.
+pub(super) fn cfg_features() -> Vec<(Span, Symbol)> {
+    let mut cfg_features: Vec<(Span, Symbol)> = CFG_FEATURES.with(|features| {
+        features
+            .borrow()
+            .iter()
+            .map(|(&span, &feature)| (span, feature))
+            .collect()
+    });
+    cfg_features.sort_by_key(|&(span, _)| span);
+    cfg_features
+}
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
+    let Some(package) = current_package(metadata) else {
+        return;
+    };
+    let manifest = Manifest::load(cx, package.manifest_path.as_std_path());
+    let feature_span = |feature: &str| {
+        manifest
+            .as_ref()
+            .and_then(|manifest| manifest.feature_span(feature))
+            .unwrap_or(DUMMY_SP)
+    };
+
+    let cfg_features = cfg_features();
+    check_undeclared(cx, package, &cfg_features);
+
+    // A build script can use the features through the `CARGO_FEATURE_<name>` variables
+    if is_only_target(cx, package)
+        && !package
+            .targets
+            .iter()
+            .any(|target| target.kind.iter().any(|kind| kind == "custom-build"))
+    {
+        for feature in unused_features(package, &cfg_features) {
+            span_lint(
+                cx,
+                UNUSED_FEATURES,
+                feature_span(feature),
+                &format!("feature `{feature}` is never used"),
+            );
+        }
+    }
+
+    if let Some(std) = default_std(package)
+        && is_unconditionally_no_std(cx)
+    {
+        span_lint_and_help(
+            cx,
+            STD_IN_DEFAULT_FEATURES,
+            feature_span("default"),
+            &format!("the default features of this `#![no_std]` crate enable `{std}`"),
+            None,
+            "remove it from the default features, so the crate can be used without `std` by default",
+        );
+    }
+
+    if let Some(manifest) = &manifest {
+        check_implicit(cx, package, manifest);
+    }
+}
+
+fn check_undeclared(cx: &LateContext<'_>, package: &Package, cfg_features: &[(Span, Symbol)]) {
+    let declared: Vec<Symbol> = package.features.keys().map(|feature| Symbol::intern(feature)).collect();
+    for &(span, feature) in cfg_features {
+        if declared.contains(&feature) {
+            continue;
+        }
+        span_lint_and_then(
+            cx,
+            UNDECLARED_FEATURES,
+            span,
+            &format!("feature `{feature}` is not declared in `Cargo.toml`"),
+            |diag| {
+                if let Some(similar) = find_best_match_for_name(&declared, feature, None) {
+                    diag.span_suggestion(
+                        span,
+                        "there is a feature with a similar name",
+                        format!("\"{similar}\""),
+                        Applicability::MaybeIncorrect,
+                    );
+                } else {
+                    diag.help("add it to the `[features]` of `Cargo.toml`");
+                }
+            },
+        );
+    }
+}
+
+/// Returns the features that aren't used in a `cfg` and don't enable any dependency or feature
+/// that is.
+fn unused_features<'a>(package: &'a Package, cfg_features: &[(Span, Symbol)]) -> Vec<&'a str> {
+    let mut used: FxHashSet<&str> = package
+        .features
+        .keys()
+        .map(String::as_str)
+        .filter(|&feature| cfg_features.iter().any(|(_, used)| used.as_str() == feature))
+        .chain(
+            package
+                .targets
+                .iter()
+                .flat_map(|target| &target.required_features)
+                .map(String::as_str),
+        )
+        .collect();
+    loop {
+        let len = used.len();
+        for (feature, values) in &package.features {
+            // `dep:name`, `name/feature` and `name?/feature` enable a dependency or its features
+            if values
+                .iter()
+                .any(|value| value.contains(':') || value.contains('/') || used.contains(value.as_str()))
+            {
+                used.insert(feature);
+            }
+        }
+        if used.len() == len {
+            break;
+        }
+    }
+    let mut unused: Vec<&str> = package
+        .features
+        .keys()
+        .map(String::as_str)
+        .filter(|feature| *feature != "default" && !used.contains(feature))
+        .collect();
+    unused.sort_unstable();
+    unused
+}
+
+/// Returns the `std` feature of the package or of a dependency enabled by the default features.
+fn default_std(package: &Package) -> Option<&str> {
+    let mut enabled = vec!["default"];
+    let mut i = 0;
+    while let Some(&feature) = enabled.get(i) {
+        i += 1;
+        for value in package.features.get(feature).into_iter().flatten() {
+            if value == "std" || value.ends_with("/std") {
+                return Some(value);
+            }
+            if !enabled.contains(&value.as_str()) {
+                enabled.push(value);
+            }
+        }
+    }
+    None
+}
+
+/// Whether the crate has a `#![no_std]` attribute, as opposed to one in a `#![cfg_attr]`.
+fn is_unconditionally_no_std(cx: &LateContext<'_>) -> bool {
+    cx.tcx.hir().attrs(CRATE_HIR_ID).iter().any(|attr| {
+        attr.has_name(sym::no_std)
+            && attr.style == AttrStyle::Inner
+            && snippet_opt(cx, attr.span).is_some_and(|snippet| snippet.starts_with("#!"))
+    })
+}
+
+/// Checks for optional dependencies enabled through their implicit feature.
+fn check_implicit(cx: &LateContext<'_>, package: &Package, manifest: &Manifest) {
+    for (value, span) in manifest.feature_values() {
+        if manifest.feature_span(value).is_none()
+            && package
+                .dependencies
+                .iter()
+                .any(|dep| dep.optional && dep.rename.as_ref().unwrap_or(&dep.name) == value)
+        {
+            span_lint_and_then(
+                cx,
+                IMPLICIT_FEATURES,
+                span,
+                &format!("the optional dependency `{value}` is enabled through its implicit feature"),
+                |diag| {
+                    diag.span_suggestion(
+                        span,
+                        "use the `dep:` syntax",
+                        format!("\"dep:{value}\""),
+                        Applicability::MaybeIncorrect,
+                    );
+                    diag.note(format!(
+                        "this removes the implicit `{value}` feature, which the dependents of the crate may be using"
+                    ));
+                },
+            );
+        }
+    }
+}
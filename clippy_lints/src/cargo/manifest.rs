@@ -0,0 +1,159 @@
+//! Spans of the keys of a `Cargo.toml`, to point the cargo lints at them
+
+use cargo_metadata::{Dependency, DependencyKind};
+use rustc_data_structures::sync::Lrc;
+use rustc_lint::{LateContext, LintContext};
+use rustc_span::source_map::SourceFile;
+use rustc_span::{BytePos, Pos, Span, SyntaxContext};
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::Path;
+use std::{env, fmt};
+use toml::Spanned;
+
+/// A `Cargo.toml` loaded into the `SourceMap`.
+pub(super) struct Manifest {
+    file: Lrc<SourceFile>,
+    toml: ManifestToml,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ManifestToml {
+    package: Option<Spanned<IgnoredAny>>,
+    #[serde(default)]
+    features: BTreeMap<Spanned<String>, Vec<Spanned<String>>>,
+    // Not flattened, the spans are lost otherwise
+    #[serde(default)]
+    dependencies: DependencyTable,
+    #[serde(default, alias = "dev_dependencies")]
+    dev_dependencies: DependencyTable,
+    #[serde(default, alias = "build_dependencies")]
+    build_dependencies: DependencyTable,
+    #[serde(default)]
+    target: BTreeMap<String, TargetToml>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TargetToml {
+    #[serde(default)]
+    dependencies: DependencyTable,
+    #[serde(default, alias = "dev_dependencies")]
+    dev_dependencies: DependencyTable,
+    #[serde(default, alias = "build_dependencies")]
+    build_dependencies: DependencyTable,
+}
+
+type DependencyTable = BTreeMap<Spanned<String>, Spanned<DependencyToml>>;
+
+/// A dependency, either `name = "1.0"` or `name = { version = "1.0", .. }`.
+struct DependencyToml {
+    version: Option<Range<usize>>,
+}
+
+impl<'de> Deserialize<'de> for DependencyToml {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DependencyVisitor;
+
+        impl<'de> Visitor<'de> for DependencyVisitor {
+            type Value = DependencyToml;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a version requirement or a dependency table")
+            }
+
+            fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+                // The span of the whole dependency is the version
+                Ok(DependencyToml { version: None })
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut version = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "version" {
+                        version = Some(map.next_value::<Spanned<IgnoredAny>>()?.span());
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                Ok(DependencyToml { version })
+            }
+        }
+
+        deserializer.deserialize_any(DependencyVisitor)
+    }
+}
+
+impl Manifest {
+    /// Loads and parses the manifest, returns `None` if that fails. Cargo reports the errors of
+    /// invalid manifests itself.
+    pub(super) fn load(cx: &LateContext<'_>, path: &Path) -> Option<Self> {
+        // Displayed relative to the working directory, like the source files passed by cargo
+        let path = env::current_dir()
+            .ok()
+            .and_then(|dir| path.strip_prefix(dir).ok())
+            .unwrap_or(path);
+        let file = cx.sess().source_map().load_file(path).ok()?;
+        let toml = toml::from_str(file.src.as_deref()?).ok()?;
+        Some(Self { file, toml })
+    }
+
+    fn span(&self, range: Range<usize>) -> Span {
+        Span::new(
+            self.file.start_pos + BytePos::from_usize(range.start),
+            self.file.start_pos + BytePos::from_usize(range.end),
+            SyntaxContext::root(),
+            None,
+        )
+    }
+
+    /// Returns the span of the `[package]` header.
+    pub(super) fn package_span(&self) -> Option<Span> {
+        let range = self.toml.package.as_ref()?.span();
+        let src = self.file.src.as_deref()?;
+        let header_len = src[range.clone()].find(['\n', '\r']).unwrap_or(range.len());
+        Some(self.span(range.start..range.start + header_len))
+    }
+
+    /// Returns the span of the name of a feature.
+    pub(super) fn feature_span(&self, name: &str) -> Option<Span> {
+        self.toml
+            .features
+            .keys()
+            .find(|key| key.get_ref() == name)
+            .map(|key| self.span(key.span()))
+    }
+
+    /// Returns the values of all the features declared in the manifest, with their spans.
+    pub(super) fn feature_values(&self) -> impl Iterator<Item = (&str, Span)> {
+        self.toml
+            .features
+            .values()
+            .flatten()
+            .map(|value| (value.get_ref().as_str(), self.span(value.span())))
+    }
+
+    /// Returns the span of the name of a dependency, and the span of its version requirement.
+    pub(super) fn dependency_spans(&self, dependency: &Dependency) -> Option<(Span, Span)> {
+        let table = match (&dependency.target, dependency.kind) {
+            (Some(target), kind) => {
+                let target = self.toml.target.get(&target.to_string())?;
+                match kind {
+                    DependencyKind::Development => &target.dev_dependencies,
+                    DependencyKind::Build => &target.build_dependencies,
+                    _ => &target.dependencies,
+                }
+            },
+            (None, DependencyKind::Development) => &self.toml.dev_dependencies,
+            (None, DependencyKind::Build) => &self.toml.build_dependencies,
+            (None, _) => &self.toml.dependencies,
+        };
+        let name = dependency.rename.as_ref().unwrap_or(&dependency.name);
+        let (key, value) = table.iter().find(|(key, _)| key.get_ref() == name)?;
+        let version = value.get_ref().version.clone().unwrap_or_else(|| value.span());
+        Some((self.span(key.span()), self.span(version)))
+    }
+}
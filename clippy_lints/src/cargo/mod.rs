@@ -1,15 +1,25 @@
 mod common_metadata;
 mod feature_name;
+mod features;
+mod manifest;
 mod multiple_crate_versions;
+mod unused_dependencies;
 mod wildcard_dependencies;
 
-use cargo_metadata::MetadataCommand;
+pub(crate) use features::{collect_attr_features, collect_crate_attr_features, collect_macro_features};
+
+use crate::utils::conf::AllowedDuplicateCrate;
+use cargo_metadata::camino::Utf8Path;
+use cargo_metadata::{Metadata, MetadataCommand, Package};
 use clippy_utils::diagnostics::span_lint;
-use clippy_utils::is_lint_allowed;
+use clippy_utils::should_skip_lint;
+use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_hir::hir_id::CRATE_HIR_ID;
 use rustc_lint::{LateContext, LateLintPass, Lint};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::DUMMY_SP;
+use std::env;
+use std::path::Path;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -128,6 +138,8 @@ declare_clippy_lint! {
     /// ### Known problems
     /// Because this can be caused purely by the dependencies
     /// themselves, it's not always possible to fix this issue.
+    /// Such crates can be listed in the `allowed-duplicate-crates`
+    /// configuration.
     ///
     /// ### Example
     /// ```toml
@@ -162,8 +174,174 @@ declare_clippy_lint! {
     "wildcard dependencies being used"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `[dependencies]` that are never used by the crate, that are only used in
+    /// tests, or that are optional but used without being gated behind one of their features.
+    ///
+    /// ### Why is this bad?
+    /// Unused dependencies slow down the build. Dependencies only used in tests belong in
+    /// `[dev-dependencies]`, so the users of the crate don't have to build them. An optional
+    /// dependency used in code that isn't gated behind one of its features breaks the build when
+    /// the features are disabled.
+    ///
+    /// ### Known problems
+    /// - Packages with more than one library or binary target are ignored, as a dependency may
+    ///   be used by the other targets.
+    /// - Uses in `#[cfg(test)]` code are only seen when compiling the tests, e.g. with
+    ///   `cargo clippy --tests`, otherwise dependencies only used in tests are reported as
+    ///   unused.
+    /// - Dependencies that are only linked, e.g. `-sys` crates or crates providing a
+    ///   `#[global_allocator]`, are reported as unused.
+    /// - Only the code enabled by the current `cfg`s is checked, so dependencies only used
+    ///   behind other `cfg`s are reported as unused. Dependencies in
+    ///   `[target.'cfg(..)'.dependencies]` are ignored for this reason.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [dependencies]
+    /// # only used by the tests
+    /// pretty_assertions = "1.4"
+    /// ```
+    ///
+    /// Use instead:
+    /// ```toml
+    /// [dev-dependencies]
+    /// pretty_assertions = "1.4"
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub UNUSED_DEPENDENCIES,
+    cargo,
+    "dependencies that are unused or only used in tests"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for features used in `cfg` attributes, `cfg!` and `cfg_if!` that aren't declared
+    /// in the `[features]` of `Cargo.toml`.
+    ///
+    /// ### Why is this bad?
+    /// Such a feature can never be enabled, the code behind it is silently compiled out. This is
+    /// usually a typo.
+    ///
+    /// ### Known problems
+    /// The `cfg`s in modules that aren't loaded, e.g. `#[cfg(test)] mod tests;` when not
+    /// compiling the tests, aren't checked.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [features]
+    /// serde = ["dep:serde"]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// #[cfg(feature = "sedre")]
+    /// impl Serialize for Foo {
+    ///     // ..
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[cfg(feature = "serde")]
+    /// impl Serialize for Foo {
+    ///     // ..
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub UNDECLARED_FEATURES,
+    cargo,
+    "features used in `cfg`s that are not declared in `Cargo.toml`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for features declared in `Cargo.toml` that are never used in `cfg`s, and that
+    /// don't enable a dependency or another feature that is.
+    ///
+    /// ### Why is this bad?
+    /// Enabling the feature has no effect.
+    ///
+    /// ### Known problems
+    /// - Packages with more than one library or binary target, or with a build script, are
+    ///   ignored, as the feature may be used by the other targets.
+    /// - Features only used in tests, examples or in modules that aren't loaded, e.g.
+    ///   `#[cfg(test)] mod tests;` when not compiling the tests, are reported.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [features]
+    /// # not used anywhere
+    /// fast-math = []
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub UNUSED_FEATURES,
+    cargo,
+    "features that are never used"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#![no_std]` crates whose default features enable their `std` feature, or the
+    /// `std` feature of a dependency.
+    ///
+    /// ### Why is this bad?
+    /// The crate is `no_std`, but its users need `default-features = false` to use it without
+    /// `std`.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [features]
+    /// default = ["std"]
+    /// std = []
+    /// ```
+    ///
+    /// Use instead:
+    /// ```toml
+    /// [features]
+    /// std = []
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub STD_IN_DEFAULT_FEATURES,
+    cargo,
+    "default features enabling `std` in a `#![no_std]` crate"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for features enabling an optional dependency through its implicit feature, i.e.
+    /// by its name instead of `dep:name`.
+    ///
+    /// ### Why is this bad?
+    /// The implicit feature exposes the dependency as a feature of the crate, which is part of
+    /// its public API. With `dep:name`, the dependency is an implementation detail.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [dependencies]
+    /// serde = { version = "1.0", optional = true }
+    ///
+    /// [features]
+    /// serialization = ["serde"]
+    /// ```
+    ///
+    /// Use instead:
+    /// ```toml
+    /// [dependencies]
+    /// serde = { version = "1.0", optional = true }
+    ///
+    /// [features]
+    /// serialization = ["dep:serde"]
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub IMPLICIT_FEATURES,
+    cargo,
+    "optional dependencies enabled without the `dep:` syntax"
+}
+
 pub struct Cargo {
     pub ignore_publish: bool,
+    pub allowed_duplicate_crates: Vec<AllowedDuplicateCrate>,
 }
 
 impl_lint_pass!(Cargo => [
@@ -171,9 +349,41 @@ impl_lint_pass!(Cargo => [
     REDUNDANT_FEATURE_NAMES,
     NEGATIVE_FEATURE_NAMES,
     MULTIPLE_CRATE_VERSIONS,
-    WILDCARD_DEPENDENCIES
+    WILDCARD_DEPENDENCIES,
+    UNUSED_DEPENDENCIES,
+    UNDECLARED_FEATURES,
+    UNUSED_FEATURES,
+    STD_IN_DEFAULT_FEATURES,
+    IMPLICIT_FEATURES
 ]);
 
+const LIB_KINDS: &[&str] = &["lib", "rlib", "dylib", "cdylib", "staticlib", "proc-macro"];
+
+/// Returns the package of the crate being compiled.
+fn current_package(metadata: &Metadata) -> Option<&Package> {
+    let dir = env::var_os("CARGO_MANIFEST_DIR")?;
+    metadata
+        .packages
+        .iter()
+        .find(|p| p.manifest_path.parent().map(Utf8Path::as_std_path) == Some(Path::new(&dir)))
+}
+
+/// Whether the crate being compiled is the only library or binary of `package`. Otherwise the
+/// other targets may use what seems unused in this crate.
+fn is_only_target(cx: &LateContext<'_>, package: &Package) -> bool {
+    let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+    let mut targets = package.targets.iter().filter(|target| {
+        target
+            .kind
+            .iter()
+            .any(|kind| kind == "bin" || LIB_KINDS.contains(&&**kind))
+    });
+    match (targets.next(), targets.next()) {
+        (Some(target), None) => target.name.replace('-', "_") == crate_name.as_str(),
+        _ => false,
+    }
+}
+
 impl LateLintPass<'_> for Cargo {
     fn check_crate(&mut self, cx: &LateContext<'_>) {
         static NO_DEPS_LINTS: &[&Lint] = &[
@@ -181,17 +391,22 @@ impl LateLintPass<'_> for Cargo {
             REDUNDANT_FEATURE_NAMES,
             NEGATIVE_FEATURE_NAMES,
             WILDCARD_DEPENDENCIES,
+            UNDECLARED_FEATURES,
+            UNUSED_FEATURES,
+            STD_IN_DEFAULT_FEATURES,
+            IMPLICIT_FEATURES,
         ];
-        static WITH_DEPS_LINTS: &[&Lint] = &[MULTIPLE_CRATE_VERSIONS];
+        static WITH_DEPS_LINTS: &[&Lint] = &[MULTIPLE_CRATE_VERSIONS, UNUSED_DEPENDENCIES];
 
         if !NO_DEPS_LINTS
             .iter()
-            .all(|&lint| is_lint_allowed(cx, lint, CRATE_HIR_ID))
+            .all(|&lint| should_skip_lint(cx, lint, CRATE_HIR_ID))
         {
             match MetadataCommand::new().no_deps().exec() {
                 Ok(metadata) => {
                     common_metadata::check(cx, &metadata, self.ignore_publish);
                     feature_name::check(cx, &metadata);
+                    features::check(cx, &metadata);
                     wildcard_dependencies::check(cx, &metadata);
                 },
                 Err(e) => {
@@ -204,11 +419,12 @@ impl LateLintPass<'_> for Cargo {
 
         if !WITH_DEPS_LINTS
             .iter()
-            .all(|&lint| is_lint_allowed(cx, lint, CRATE_HIR_ID))
+            .all(|&lint| should_skip_lint(cx, lint, CRATE_HIR_ID))
         {
             match MetadataCommand::new().exec() {
                 Ok(metadata) => {
-                    multiple_crate_versions::check(cx, &metadata);
+                    multiple_crate_versions::check(cx, &metadata, &self.allowed_duplicate_crates);
+                    unused_dependencies::check(cx, &metadata);
                 },
                 Err(e) => {
                     for lint in WITH_DEPS_LINTS {
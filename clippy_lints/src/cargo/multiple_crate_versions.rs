@@ -1,16 +1,24 @@
 //! lint on multiple versions of a crate being used
 
-use cargo_metadata::{DependencyKind, Metadata, Node, Package, PackageId};
-use clippy_utils::diagnostics::span_lint;
+use cargo_metadata::{DependencyKind, Metadata, Node, NodeDep, Package, PackageId};
+use clippy_utils::diagnostics::span_lint_and_then;
 use if_chain::if_chain;
 use itertools::Itertools;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_lint::LateContext;
 use rustc_span::source_map::DUMMY_SP;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
 
+use super::manifest::Manifest;
 use super::MULTIPLE_CRATE_VERSIONS;
+use crate::utils::conf::AllowedDuplicateCrate;
 
-pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
+/// The maximum number of dependency chains listed for each version.
+const LISTED_CHAINS: usize = 5;
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata, allowed_duplicate_crates: &[AllowedDuplicateCrate]) {
     let local_name = cx.tcx.crate_name(LOCAL_CRATE);
     let mut packages = metadata.packages.clone();
     packages.sort_by(|a, b| a.name.cmp(&b.name));
@@ -21,24 +29,27 @@ pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
             .iter()
             .find_map(|p| if p.name == local_name.as_str() { Some(&p.id) } else { None });
         then {
+            let graph = Graph {
+                packages: packages.iter().map(|p| (&p.id, p)).collect(),
+                nodes: resolve.nodes.iter().map(|node| (&node.id, node)).collect(),
+            };
+
             for (name, group) in &packages.iter().group_by(|p| p.name.clone()) {
-                let group: Vec<&Package> = group.collect();
+                let mut group: Vec<&Package> = group
+                    .filter(|p| {
+                        !allowed_duplicate_crates
+                            .iter()
+                            .any(|allowed| allowed.matches(&p.name, &p.version))
+                    })
+                    .collect();
 
                 if group.len() <= 1 {
                     continue;
                 }
 
                 if group.iter().all(|p| is_normal_dep(&resolve.nodes, local_id, &p.id)) {
-                    let mut versions: Vec<_> = group.into_iter().map(|p| &p.version).collect();
-                    versions.sort();
-                    let versions = versions.iter().join(", ");
-
-                    span_lint(
-                        cx,
-                        MULTIPLE_CRATE_VERSIONS,
-                        DUMMY_SP,
-                        &format!("multiple versions for dependency `{name}`: {versions}"),
-                    );
+                    group.sort_by(|a, b| a.version.cmp(&b.version));
+                    report(cx, &graph, local_id, &name, &group);
                 }
             }
         }
@@ -61,3 +72,145 @@ fn is_normal_dep(nodes: &[Node], local_id: &PackageId, dep_id: &PackageId) -> bo
         .filter(|node| depends_on(node, dep_id))
         .any(|node| node.id == *local_id || is_normal_dep(nodes, local_id, &node.id))
 }
+
+struct Graph<'a> {
+    packages: FxHashMap<&'a PackageId, &'a Package>,
+    nodes: FxHashMap<&'a PackageId, &'a Node>,
+}
+
+impl<'a> Graph<'a> {
+    fn normal_deps(&self, id: &PackageId) -> impl Iterator<Item = &'a NodeDep> + '_ {
+        self.nodes
+            .get(id)
+            .into_iter()
+            .flat_map(|node| &node.deps)
+            .filter(|dep| is_normal(dep))
+    }
+
+    /// Returns the shortest chain of normal dependencies from `from` to `to`, both included.
+    fn chain(&self, from: &'a PackageId, to: &PackageId) -> Option<Vec<&'a PackageId>> {
+        let mut parents = FxHashMap::default();
+        let mut visited = FxHashSet::from_iter([from]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(mut id) = queue.pop_front() {
+            if id == to {
+                let mut chain = vec![id];
+                while let Some(&parent) = parents.get(id) {
+                    chain.push(parent);
+                    id = parent;
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            for dep in self.normal_deps(id) {
+                if visited.insert(&dep.pkg) {
+                    parents.insert(&dep.pkg, id);
+                    queue.push_back(&dep.pkg);
+                }
+            }
+        }
+        None
+    }
+
+    fn display(&self, id: &PackageId) -> String {
+        match self.packages.get(id) {
+            Some(package) => format!("{} {}", package.name, package.version),
+            None => id.repr.clone(),
+        }
+    }
+}
+
+/// Emits the lint for the `duplicates` of the crate `name`, sorted by version, with the dependency
+/// chains through which each version is used. The direct dependencies that only use older versions
+/// are suggested to be updated.
+fn report(cx: &LateContext<'_>, graph: &Graph<'_>, local_id: &PackageId, name: &str, duplicates: &[&Package]) {
+    // The chains from every direct dependency to each of the versions
+    let direct: Vec<(&NodeDep, Vec<Option<Vec<&PackageId>>>)> = graph
+        .normal_deps(local_id)
+        .map(|dep| {
+            let chains = duplicates.iter().map(|p| graph.chain(&dep.pkg, &p.id)).collect();
+            (dep, chains)
+        })
+        .filter(|(_, chains): &(_, Vec<_>)| chains.iter().any(Option::is_some))
+        .collect();
+    let newest = duplicates[duplicates.len() - 1];
+    let outdated: Vec<&Package> = direct
+        .iter()
+        .filter(|(_, chains)| chains[chains.len() - 1].is_none())
+        .filter_map(|(dep, _)| graph.packages.get(&dep.pkg).copied())
+        .collect();
+
+    let local = graph.packages[local_id];
+    let span = Manifest::load(cx, local.manifest_path.as_std_path())
+        .and_then(|manifest| {
+            outdated
+                .iter()
+                .copied()
+                .chain(
+                    direct
+                        .iter()
+                        .filter_map(|(dep, _)| graph.packages.get(&dep.pkg).copied()),
+                )
+                .find_map(|package| {
+                    let dependency = local
+                        .dependencies
+                        .iter()
+                        .find(|d| d.name == package.name && matches!(d.kind, DependencyKind::Normal))?;
+                    Some(manifest.dependency_spans(dependency)?.0)
+                })
+        })
+        .unwrap_or(DUMMY_SP);
+
+    let versions = duplicates.iter().map(|p| &p.version).join(", ");
+    span_lint_and_then(
+        cx,
+        MULTIPLE_CRATE_VERSIONS,
+        span,
+        &format!("multiple versions for dependency `{name}`: {versions}"),
+        |diag| {
+            for (i, package) in duplicates.iter().enumerate() {
+                let chains: Vec<String> = direct
+                    .iter()
+                    .filter_map(|(_, chains)| chains[i].as_ref())
+                    .map(|chain| {
+                        std::iter::once(local_id)
+                            .chain(chain.iter().copied())
+                            .map(|id| graph.display(id))
+                            .join(" -> ")
+                    })
+                    .collect();
+                let mut note = format!("`{name}` {} is used through:", package.version);
+                for chain in chains.iter().take(LISTED_CHAINS) {
+                    note.push_str("\n    ");
+                    note.push_str(chain);
+                }
+                if chains.len() > LISTED_CHAINS {
+                    let _ = write!(note, "\n    and {} more", chains.len() - LISTED_CHAINS);
+                }
+                diag.note(note);
+            }
+            let (itself, others): (Vec<&Package>, Vec<&Package>) = outdated.iter().partition(|p| p.name == name);
+            let mut updates = Vec::new();
+            if !itself.is_empty() {
+                updates.push(format!("`{name}` to {}", newest.version));
+            }
+            if !others.is_empty() {
+                let versions = if others.len() == 1 { "a version" } else { "versions" };
+                updates.push(format!(
+                    "{} to {versions} using `{name}` {}",
+                    others.iter().map(|p| format!("`{}`", p.name)).join(", "),
+                    newest.version
+                ));
+            }
+            if !updates.is_empty() {
+                diag.help(format!("updating {} may unify the versions", updates.join(" and ")));
+            }
+        },
+    );
+}
+
+fn is_normal(dep: &NodeDep) -> bool {
+    dep.dep_kinds
+        .iter()
+        .any(|info| matches!(info.kind, DependencyKind::Normal))
+}
@@ -0,0 +1,220 @@
+use cargo_metadata::{DependencyKind, Metadata, Package};
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::macros::macro_backtrace;
+use clippy_utils::{is_in_cfg_test, is_in_test_function};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::{CrateNum, DefId};
+use rustc_hir::intravisit::{walk_body, walk_expr, walk_item, walk_path, Visitor};
+use rustc_hir::{Body, Expr, HirId, Item, ItemKind, Path};
+use rustc_lint::{LateContext, LintContext};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::{TyCtxt, TypeckResults};
+use rustc_session::cstore::ExternCrate;
+use rustc_span::source_map::DUMMY_SP;
+use rustc_span::{Span, Symbol};
+
+use super::features::cfg_features;
+use super::manifest::Manifest;
+use super::{current_package, is_only_target, LIB_KINDS, UNUSED_DEPENDENCIES};
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
+    let Some(package) = current_package(metadata).filter(|package| is_only_target(cx, package)) else {
+        return;
+    };
+
+    let mut visitor = UsedCrates {
+        tcx: cx.tcx,
+        maybe_typeck_results: None,
+        used: FxHashMap::default(),
+    };
+    cx.tcx.hir().visit_all_item_likes_in_crate(&mut visitor);
+    let cfg_features: FxHashSet<Symbol> = cfg_features().into_iter().map(|(_, feature)| feature).collect();
+    let is_test = cx.sess().opts.test;
+    let manifest = Manifest::load(cx, package.manifest_path.as_std_path());
+
+    for dependency in &package.dependencies {
+        let extern_name = dependency.rename.as_ref().unwrap_or(&dependency.name).replace('-', "_");
+        // Dependencies of other platforms or disabled optional dependencies aren't passed to rustc.
+        // Platform-specific dependencies are usually only used under a `#[cfg]` matching their
+        // platform, which may not be active even if the dependency is.
+        if !matches!(dependency.kind, DependencyKind::Normal)
+            || dependency.target.is_some()
+            || cx.sess().opts.externs.get(&extern_name).is_none()
+        {
+            continue;
+        }
+        let Some(crate_name) = lib_name(metadata, package, &extern_name) else {
+            continue;
+        };
+        let span = manifest
+            .as_ref()
+            .and_then(|manifest| manifest.dependency_spans(dependency))
+            .map_or(DUMMY_SP, |(key, _)| key);
+
+        match direct_crate(cx.tcx, &crate_name).and_then(|cnum| visitor.used.get(&cnum)) {
+            None => span_lint_and_help(
+                cx,
+                UNUSED_DEPENDENCIES,
+                span,
+                &format!("dependency `{}` is never used", dependency.name),
+                None,
+                "remove it, or move it to `[dev-dependencies]` if it's only used in tests",
+            ),
+            Some(false) if is_test => span_lint_and_help(
+                cx,
+                UNUSED_DEPENDENCIES,
+                span,
+                &format!("dependency `{}` is only used in tests", dependency.name),
+                None,
+                "move it to `[dev-dependencies]`",
+            ),
+            Some(_) => {
+                if dependency.optional
+                    && !enabling_features(package, dependency.rename.as_ref().unwrap_or(&dependency.name))
+                        .iter()
+                        .any(|feature| cfg_features.contains(&Symbol::intern(feature)))
+                {
+                    span_lint_and_help(
+                        cx,
+                        UNUSED_DEPENDENCIES,
+                        span,
+                        &format!(
+                            "optional dependency `{}` is used without being gated behind its features",
+                            dependency.name
+                        ),
+                        None,
+                        "gate the code using it with `#[cfg(feature = \"..\")]`, or make it a required dependency",
+                    );
+                }
+            },
+        }
+    }
+}
+
+/// Returns the crate name of the library of the dependency `extern_name` of `package`.
+fn lib_name(metadata: &Metadata, package: &Package, extern_name: &str) -> Option<String> {
+    let node = metadata
+        .resolve
+        .as_ref()?
+        .nodes
+        .iter()
+        .find(|node| node.id == package.id)?;
+    let dep = node.deps.iter().find(|dep| dep.name == extern_name)?;
+    let dep_package = metadata.packages.iter().find(|p| p.id == dep.pkg)?;
+    dep_package
+        .targets
+        .iter()
+        .find(|target| target.kind.iter().any(|kind| LIB_KINDS.contains(&&**kind)))
+        .map(|target| target.name.replace('-', "_"))
+}
+
+/// Finds the direct dependency named `name` among the loaded crates. Dependencies that are never
+/// referred to are not loaded at all.
+fn direct_crate(tcx: TyCtxt<'_>, name: &str) -> Option<CrateNum> {
+    let mut crates = tcx.crates(()).iter().copied().filter(|&cnum| {
+        tcx.crate_name(cnum).as_str() == name && tcx.extern_crate(cnum.as_def_id()).is_some_and(ExternCrate::is_direct)
+    });
+    match (crates.next(), crates.next()) {
+        (Some(cnum), None) => Some(cnum),
+        _ => None,
+    }
+}
+
+/// Returns the features enabling the optional dependency `name`, directly or through other
+/// features.
+fn enabling_features<'a>(package: &'a Package, name: &str) -> Vec<&'a str> {
+    fn enables(value: &str, name: &str, enabling: &[&str]) -> bool {
+        if let Some(dep) = value.strip_prefix("dep:") {
+            return dep == name;
+        }
+        match value.split_once('/') {
+            // `dep?/feature` doesn't enable `dep`
+            Some((dep, _)) => dep == name,
+            None => value == name || enabling.contains(&value),
+        }
+    }
+
+    let mut enabling: Vec<&str> = Vec::new();
+    loop {
+        let len = enabling.len();
+        for (feature, values) in &package.features {
+            if !enabling.contains(&feature.as_str()) && values.iter().any(|value| enables(value, name, &enabling)) {
+                enabling.push(feature);
+            }
+        }
+        if enabling.len() == len {
+            return enabling;
+        }
+    }
+}
+
+/// Collects the crates whose items are used, and whether they're used outside of tests.
+struct UsedCrates<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    maybe_typeck_results: Option<&'tcx TypeckResults<'tcx>>,
+    used: FxHashMap<CrateNum, bool>,
+}
+
+impl<'tcx> UsedCrates<'tcx> {
+    fn mark(&mut self, def_id: DefId, hir_id: HirId) {
+        if def_id.is_local() || self.used.get(&def_id.krate) == Some(&true) {
+            return;
+        }
+        let in_test = is_in_cfg_test(self.tcx, hir_id) || is_in_test_function(self.tcx, hir_id);
+        *self.used.entry(def_id.krate).or_default() |= !in_test;
+    }
+
+    fn mark_macros(&mut self, span: Span, hir_id: HirId) {
+        for macro_call in macro_backtrace(span) {
+            self.mark(macro_call.def_id, hir_id);
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UsedCrates<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_body(&mut self, body: &'tcx Body<'tcx>) {
+        let old_maybe_typeck_results = self.maybe_typeck_results.replace(self.tcx.typeck_body(body.id()));
+        walk_body(self, body);
+        self.maybe_typeck_results = old_maybe_typeck_results;
+    }
+
+    fn visit_item(&mut self, item: &'tcx Item<'tcx>) {
+        if let ItemKind::ExternCrate(_) = item.kind
+            && let Some(cnum) = self.tcx.extern_mod_stmt_cnum(item.owner_id.def_id)
+        {
+            self.mark(cnum.as_def_id(), item.hir_id());
+        }
+        self.mark_macros(item.span, item.hir_id());
+        walk_item(self, item);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if let Some(def_id) = self
+            .maybe_typeck_results
+            .and_then(|typeck_results| typeck_results.type_dependent_def_id(expr.hir_id))
+        {
+            self.mark(def_id, expr.hir_id);
+        }
+        self.mark_macros(expr.span, expr.hir_id);
+        walk_expr(self, expr);
+    }
+
+    fn visit_path(&mut self, path: &Path<'tcx>, hir_id: HirId) {
+        // The crate root of `dep::Item` is the dependency even if `Item` is a re-export
+        for def_id in path
+            .segments
+            .iter()
+            .filter_map(|segment| segment.res.opt_def_id())
+            .chain(path.res.opt_def_id())
+        {
+            self.mark(def_id, hir_id);
+        }
+        walk_path(self, path);
+    }
+}
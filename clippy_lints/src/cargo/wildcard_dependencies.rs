@@ -4,10 +4,13 @@ use if_chain::if_chain;
 use rustc_lint::LateContext;
 use rustc_span::source_map::DUMMY_SP;
 
+use super::manifest::Manifest;
 use super::WILDCARD_DEPENDENCIES;
 
 pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
-    for dep in &metadata.packages[0].dependencies {
+    let package = &metadata.packages[0];
+    let manifest = Manifest::load(cx, package.manifest_path.as_std_path());
+    for dep in &package.dependencies {
         // VersionReq::any() does not work
         if_chain! {
             if let Ok(wildcard_ver) = semver::VersionReq::parse("*");
@@ -15,10 +18,14 @@ pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
             if !source.starts_with("git");
             if dep.req == wildcard_ver;
             then {
+                let span = manifest
+                    .as_ref()
+                    .and_then(|manifest| manifest.dependency_spans(dep))
+                    .map_or(DUMMY_SP, |(_, version)| version);
                 span_lint(
                     cx,
                     WILDCARD_DEPENDENCIES,
-                    DUMMY_SP,
+                    span,
                     &format!("wildcard dependency for `{}`", dep.name),
                 );
             }
@@ -4,7 +4,8 @@ use clippy_utils::ty::{is_interior_mut_ty, needs_ordered_drop};
 use clippy_utils::visitors::for_each_expr;
 use clippy_utils::{
     capture_local_usage, def_path_def_ids, eq_expr_value, find_binding_init, get_enclosing_block, hash_expr, hash_stmt,
-    if_sequence, is_else_clause, is_lint_allowed, path_to_local, search_same, ContainsName, HirEqInterExpr, SpanlessEq,
+    if_sequence, is_else_clause, path_to_local, search_same, should_skip_lint, ContainsName, HirEqInterExpr,
+    SpanlessEq,
 };
 use core::iter;
 use core::ops::ControlFlow;
@@ -196,7 +197,7 @@ impl<'tcx> LateLintPass<'tcx> for CopyAndPaste {
             lint_same_cond(cx, &conds, &self.ignored_ty_ids);
             lint_same_fns_in_if_cond(cx, &conds);
             let all_same =
-                !is_lint_allowed(cx, IF_SAME_THEN_ELSE, expr.hir_id) && lint_if_same_then_else(cx, &conds, &blocks);
+                !should_skip_lint(cx, IF_SAME_THEN_ELSE, expr.hir_id) && lint_if_same_then_else(cx, &conds, &blocks);
             if !all_same && conds.len() != blocks.len() {
                 lint_branches_sharing_code(cx, &conds, &blocks, expr);
             }
@@ -5,7 +5,7 @@ use clippy_utils::source::{snippet_with_applicability, snippet_with_context};
 use clippy_utils::sugg::has_enclosing_paren;
 use clippy_utils::ty::{implements_trait, is_copy, peel_mid_ty_refs};
 use clippy_utils::{
-    expr_use_ctxt, get_parent_expr, get_parent_node, is_lint_allowed, path_to_local, DefinedTy, ExprUseNode,
+    expr_use_ctxt, get_parent_expr, get_parent_node, path_to_local, should_skip_lint, DefinedTy, ExprUseNode,
 };
 
 use hir::def::DefKind;
@@ -336,7 +336,7 @@ impl<'tcx> LateLintPass<'tcx> for Dereferencing<'tcx> {
                         }
                     },
                     (_, RefOp::Method { mutbl, is_ufcs })
-                        if !is_lint_allowed(cx, EXPLICIT_DEREF_METHODS, expr.hir_id)
+                        if !should_skip_lint(cx, EXPLICIT_DEREF_METHODS, expr.hir_id)
                             // Allow explicit deref in method chains. e.g. `foo.deref().bar()`
                             && (is_ufcs || !in_postfix_position(cx, expr)) =>
                     {
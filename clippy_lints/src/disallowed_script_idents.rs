@@ -2,6 +2,7 @@ use clippy_utils::diagnostics::span_lint;
 use rustc_ast::ast;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_lint::{EarlyContext, EarlyLintPass, Level, LintContext};
+use rustc_middle::lint::LintLevelSource;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use unicode_script::{Script, UnicodeScript};
 
@@ -67,8 +68,11 @@ impl EarlyLintPass for DisallowedScriptIdents {
         // Implementation is heavily inspired by the implementation of [`non_ascii_idents`] lint:
         // https://github.com/rust-lang/rust/blob/master/compiler/rustc_lint/src/non_ascii_idents.rs
 
-        let check_disallowed_script_idents = cx.builder.lint_level(DISALLOWED_SCRIPT_IDENTS).0 != Level::Allow;
-        if !check_disallowed_script_idents {
+        // When the lint is allowed by an attribute, the identifiers are still checked so that the
+        // attribute is only recorded as used if it suppressed the lint
+        if let (Level::Allow, source) = cx.builder.lint_level(DISALLOWED_SCRIPT_IDENTS)
+            && !matches!(source, LintLevelSource::Node { .. })
+        {
             return;
         }
 
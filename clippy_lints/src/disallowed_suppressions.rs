@@ -0,0 +1,189 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use regex::Regex;
+use rustc_ast::Attribute;
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::CRATE_HIR_ID;
+use rustc_lint::{LateContext, LateLintPass, LintContext, LintId};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span, Symbol};
+
+use crate::utils::conf;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies the `#[allow]` and `#[expect]` attributes of the lints configured with
+    /// `disallowed-suppressions` in clippy.toml. Lints configured with a `reason` pattern can be
+    /// suppressed if the attribute has a reason matching it.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// lints are defined in the clippy.toml file.
+    ///
+    /// The lint is emitted at the level it has at the crate root, so it can't be allowed by the
+    /// attributes it checks.
+    ///
+    /// ### Why is this bad?
+    /// Some lints guard invariants of a project, e.g. no panics in a service, and should be fixed
+    /// rather than suppressed. Others may be suppressed, but each suppression should be tracked,
+    /// e.g. by the ID of a ticket.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-suppressions = [
+    ///     "clippy::unwrap_used",
+    ///     { lint = "clippy::indexing_slicing", reason = "^TICKET-[0-9]+" },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// #[allow(clippy::unwrap_used)]
+    /// fn parse(s: &str) -> u32 {
+    ///     s.parse().unwrap()
+    /// }
+    ///
+    /// #[allow(clippy::indexing_slicing)]
+    /// fn first(v: &[u32]) -> u32 {
+    ///     v[0]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn parse(s: &str) -> Result<u32, ParseIntError> {
+    ///     s.parse()
+    /// }
+    ///
+    /// #[allow(clippy::indexing_slicing, reason = "TICKET-1234: callers check the length")]
+    /// fn first(v: &[u32]) -> u32 {
+    ///     v[0]
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub DISALLOWED_SUPPRESSIONS,
+    style,
+    "suppression of a lint disallowed by the configuration"
+}
+
+pub struct DisallowedSuppressions {
+    conf_disallowed: Vec<conf::DisallowedSuppression>,
+    /// The lints of each entry of the configuration, with the pattern their reason has to match
+    disallowed: Vec<(FxHashSet<LintId>, Option<Regex>)>,
+}
+
+impl DisallowedSuppressions {
+    pub fn new(conf_disallowed: Vec<conf::DisallowedSuppression>) -> Self {
+        Self {
+            conf_disallowed,
+            disallowed: Vec::new(),
+        }
+    }
+}
+
+/// An `#[allow]` or `#[expect]` attribute.
+pub struct Suppression {
+    pub level: Symbol,
+    /// The names of the suppressed lints and lint groups, as written in the attribute
+    pub lints: Vec<(String, Span)>,
+    pub reason: Option<Symbol>,
+}
+
+impl Suppression {
+    pub fn from_attr(attr: &Attribute) -> Option<Self> {
+        let level = attr.ident()?.name;
+        if !matches!(level, sym::allow | sym::expect) {
+            return None;
+        }
+        let mut lints = Vec::new();
+        let mut reason = None;
+        for item in attr.meta_item_list()? {
+            let Some(item) = item.meta_item() else {
+                continue;
+            };
+            if item.has_name(sym::reason) {
+                reason = item.value_str();
+            } else if item.is_word() {
+                lints.push((pprust::path_to_string(&item.path), item.span));
+            }
+        }
+        Some(Self { level, lints, reason })
+    }
+}
+
+impl_lint_pass!(DisallowedSuppressions => [DISALLOWED_SUPPRESSIONS]);
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedSuppressions {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for conf in &self.conf_disallowed {
+            let Ok(lints) = cx.lint_store.find_lints(conf.lint()) else {
+                cx.sess().err(format!(
+                    "error reading Clippy's configuration file: unknown lint `{}` in `disallowed-suppressions`",
+                    conf.lint()
+                ));
+                continue;
+            };
+            let pattern = match conf {
+                conf::DisallowedSuppression::Simple(_) => None,
+                conf::DisallowedSuppression::WithReason { reason, .. } => match Regex::new(reason) {
+                    Ok(pattern) => Some(pattern),
+                    Err(e) => {
+                        cx.sess().err(format!(
+                            "error reading Clippy's configuration file: invalid `reason` pattern for `{}`: {e}",
+                            conf.lint()
+                        ));
+                        continue;
+                    },
+                },
+            };
+            self.disallowed.push((lints.into_iter().collect(), pattern));
+        }
+    }
+
+    fn check_attribute(&mut self, cx: &LateContext<'tcx>, attr: &'tcx Attribute) {
+        if self.disallowed.is_empty() || in_external_macro(cx.sess(), attr.span) {
+            return;
+        }
+        let Some(suppression) = Suppression::from_attr(attr) else {
+            return;
+        };
+        for (name, span) in &suppression.lints {
+            let Ok(suppressed) = cx.lint_store.find_lints(name) else {
+                continue;
+            };
+            let violation = self.disallowed.iter().find_map(|(lints, pattern)| {
+                let lint = suppressed.iter().find(|lint| lints.contains(lint))?;
+                match pattern {
+                    Some(pattern)
+                        if suppression
+                            .reason
+                            .is_some_and(|reason| pattern.is_match(reason.as_str())) =>
+                    {
+                        None
+                    },
+                    _ => Some((lint, pattern)),
+                }
+            });
+            let Some((lint, pattern)) = violation else {
+                continue;
+            };
+            let lint = lint.to_string();
+            let msg = match pattern {
+                Some(pattern) => format!("suppression of `{lint}` without a reason matching `{pattern}`"),
+                None => format!("suppression of a disallowed lint `{lint}`"),
+            };
+            // Emitted at the crate root, so that the attribute can't allow it
+            span_lint_hir_and_then(cx, DISALLOWED_SUPPRESSIONS, CRATE_HIR_ID, *span, &msg, |diag| {
+                if *name != lint {
+                    diag.note(format!("`{name}` includes `{lint}`"));
+                }
+                if pattern.is_some() && suppression.reason.is_none() {
+                    diag.help(format!(
+                        "add a reason at the end of the attribute with `{}(.., reason = \"..\")`",
+                        suppression.level
+                    ));
+                }
+            });
+        }
+    }
+}
@@ -1,13 +1,14 @@
 use arrayvec::ArrayVec;
 use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_and_then};
-use clippy_utils::is_diag_trait_item;
 use clippy_utils::macros::{
-    find_format_arg_expr, find_format_args, format_arg_removal_span, format_placeholder_format_span, is_assert_macro,
-    is_format_macro, is_panic, root_macro_call, root_macro_call_first_node, FormatParamUsage,
+    find_format_arg_expr, find_format_args, first_node_macro_backtrace, format_arg_removal_span,
+    format_placeholder_format_span, is_assert_macro, is_format_macro, is_panic, root_macro_call,
+    root_macro_call_first_node, FormatParamUsage, MacroCall,
 };
 use clippy_utils::msrvs::{self, Msrv};
 use clippy_utils::source::snippet_opt;
 use clippy_utils::ty::{implements_trait, is_type_lang_item};
+use clippy_utils::{def_path_def_ids, is_diag_trait_item};
 use if_chain::if_chain;
 use itertools::Itertools;
 use rustc_ast::{
@@ -21,8 +22,9 @@ use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_middle::ty::adjustment::{Adjust, Adjustment};
 use rustc_middle::ty::Ty;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
-use rustc_span::def_id::DefId;
+use rustc_span::def_id::{DefId, DefIdSet};
 use rustc_span::edition::Edition::Edition2021;
+use rustc_span::hygiene::MacroKind;
 use rustc_span::{sym, Span, Symbol};
 
 declare_clippy_lint! {
@@ -172,26 +174,53 @@ impl_lint_pass!(FormatArgs => [
 pub struct FormatArgs {
     msrv: Msrv,
     ignore_mixed: bool,
+    format_like_macros: Vec<String>,
+    /// The `DefId`s of `format_like_macros`, resolved in `check_crate`.
+    format_like_def_ids: DefIdSet,
 }
 
 impl FormatArgs {
     #[must_use]
-    pub fn new(msrv: Msrv, allow_mixed_uninlined_format_args: bool) -> Self {
+    pub fn new(msrv: Msrv, allow_mixed_uninlined_format_args: bool, format_like_macros: Vec<String>) -> Self {
         Self {
             msrv,
             ignore_mixed: allow_mixed_uninlined_format_args,
+            format_like_macros,
+            format_like_def_ids: DefIdSet::default(),
         }
     }
+
+    /// Returns the format macro call generated by one of the `format_like_macros` derives, e.g.
+    /// the `write!` generated for an `#[error("..")]` attribute of `thiserror::Error`.
+    fn derived_format_macro_call(&self, cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<MacroCall> {
+        let root = root_macro_call(expr.span)?;
+        if root.kind != MacroKind::Derive || !self.format_like_def_ids.contains(&root.def_id) {
+            return None;
+        }
+        first_node_macro_backtrace(cx, expr).find(|macro_call| {
+            is_format_macro(cx, macro_call.def_id) && macro_call.span.ctxt().outer_expn() == root.expn
+        })
+    }
 }
 
 impl<'tcx> LateLintPass<'tcx> for FormatArgs {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in &self.format_like_macros {
+            let path: Vec<&str> = path.split("::").collect();
+            self.format_like_def_ids.extend(def_path_def_ids(cx, &path));
+        }
+    }
+
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
-        let Some(macro_call) = root_macro_call_first_node(cx, expr) else {
+        let (macro_call, derived) = if let Some(macro_call) = root_macro_call_first_node(cx, expr)
+            && (is_format_macro(cx, macro_call.def_id) || self.format_like_def_ids.contains(&macro_call.def_id))
+        {
+            (macro_call, false)
+        } else if let Some(macro_call) = self.derived_format_macro_call(cx, expr) {
+            (macro_call, true)
+        } else {
             return;
         };
-        if !is_format_macro(cx, macro_call.def_id) {
-            return;
-        }
         let name = cx.tcx.item_name(macro_call.def_id);
 
         find_format_args(cx, expr, macro_call.expn, |format_args| {
@@ -212,13 +241,21 @@ impl<'tcx> LateLintPass<'tcx> for FormatArgs {
                     }
 
                     if let Ok(arg_hir_expr) = arg_expr {
-                        check_format_in_format_args(cx, macro_call.span, name, arg_hir_expr);
+                        // The call generated by a derive points to the derive, use the argument
+                        // in the attribute instead
+                        let call_site = if derived {
+                            arg_hir_expr.span.source_callsite()
+                        } else {
+                            macro_call.span
+                        };
+                        check_format_in_format_args(cx, call_site, name, arg_hir_expr);
                         check_to_string_in_format_args(cx, name, arg_hir_expr);
                     }
                 }
             }
 
-            if self.msrv.meets(msrvs::FORMAT_ARGS_CAPTURE) {
+            // The arguments of derives such as `thiserror::Error` don't use the syntax of expressions
+            if !derived && self.msrv.meets(msrvs::FORMAT_ARGS_CAPTURE) {
                 check_uninlined_args(cx, format_args, macro_call.span, macro_call.def_id, self.ignore_mixed);
             }
         });
@@ -1,10 +1,15 @@
-use clippy_utils::diagnostics::{span_lint, span_lint_and_sugg};
-use clippy_utils::macros::{find_format_arg_expr, find_format_args, is_format_macro, root_macro_call_first_node};
-use clippy_utils::{get_parent_as_impl, is_diag_trait_item, path_to_local, peel_ref_operators};
+use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_sugg};
+use clippy_utils::macros::{
+    find_format_arg_expr, find_format_args, format_args_inputs_span, is_format_macro, root_macro_call,
+    root_macro_call_first_node,
+};
+use clippy_utils::source::snippet_with_context;
+use clippy_utils::ty::{implements_trait, is_type_lang_item, match_type};
+use clippy_utils::{get_parent_as_impl, is_diag_trait_item, is_trait_method, path_to_local, paths, peel_ref_operators};
 use if_chain::if_chain;
 use rustc_ast::{FormatArgsPiece, FormatTrait};
 use rustc_errors::Applicability;
-use rustc_hir::{Expr, ExprKind, Impl, ImplItem, ImplItemKind, QPath};
+use rustc_hir::{Expr, ExprKind, Impl, ImplItem, ImplItemKind, LangItem, QPath};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::symbol::kw;
@@ -90,6 +95,64 @@ declare_clippy_lint! {
     "use of a print macro in a formatting trait impl"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `String`s allocated only to be written to the `Formatter` in an implementation
+    /// of a formatting trait: `format!` or `to_string()` results passed to `write_str`, and
+    /// iterators collected into a `String`.
+    ///
+    /// ### Why is this bad?
+    /// The `Formatter` can be written to directly, without allocating an intermediate `String`.
+    /// Formatting is often done in hot paths such as logging, where the allocations add up.
+    ///
+    /// ### Example
+    /// ```rust
+    /// use std::fmt;
+    ///
+    /// struct Point(i32, i32);
+    /// struct Path(Vec<Point>);
+    ///
+    /// impl fmt::Display for Point {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str(&format!("({}, {})", self.0, self.1))
+    ///     }
+    /// }
+    ///
+    /// impl fmt::Display for Path {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         let points: String = self.0.iter().map(|p| p.to_string()).collect();
+    ///         f.write_str(&points)
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// use std::fmt;
+    ///
+    /// struct Point(i32, i32);
+    /// struct Path(Vec<Point>);
+    ///
+    /// impl fmt::Display for Point {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "({}, {})", self.0, self.1)
+    ///     }
+    /// }
+    ///
+    /// impl fmt::Display for Path {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         for point in &self.0 {
+    ///             write!(f, "{point}")?;
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub ALLOCATION_IN_FORMAT_IMPL,
+    perf,
+    "allocating a `String` to write it to the `Formatter` in a formatting trait impl"
+}
+
 #[derive(Clone, Copy)]
 struct FormatTraitNames {
     /// e.g. `sym::Display`
@@ -112,7 +175,7 @@ impl FormatImpl {
     }
 }
 
-impl_lint_pass!(FormatImpl => [RECURSIVE_FORMAT_IMPL, PRINT_IN_FORMAT_IMPL]);
+impl_lint_pass!(FormatImpl => [RECURSIVE_FORMAT_IMPL, PRINT_IN_FORMAT_IMPL, ALLOCATION_IN_FORMAT_IMPL]);
 
 impl<'tcx> LateLintPass<'tcx> for FormatImpl {
     fn check_impl_item(&mut self, cx: &LateContext<'_>, impl_item: &ImplItem<'_>) {
@@ -137,6 +200,7 @@ impl<'tcx> LateLintPass<'tcx> for FormatImpl {
 
         check_self_in_format_args(cx, expr, format_trait_impl);
         check_print_in_format_impl(cx, expr, format_trait_impl);
+        check_allocation_in_format_impl(cx, expr, format_trait_impl);
     }
 }
 
@@ -245,6 +309,79 @@ fn check_print_in_format_impl(cx: &LateContext<'_>, expr: &Expr<'_>, impl_trait:
     }
 }
 
+fn check_allocation_in_format_impl(cx: &LateContext<'_>, expr: &Expr<'_>, impl_trait: FormatTraitNames) {
+    if expr.span.from_expansion() {
+        return;
+    }
+    let ExprKind::MethodCall(path, receiver, args, _) = expr.kind else {
+        return;
+    };
+
+    if path.ident.name == sym!(collect)
+        && is_trait_method(cx, expr, sym::Iterator)
+        && is_type_lang_item(cx, cx.typeck_results().expr_ty(expr), LangItem::String)
+    {
+        span_lint_and_help(
+            cx,
+            ALLOCATION_IN_FORMAT_IMPL,
+            expr.span,
+            &format!("collecting into a `String` in `{}` impl", impl_trait.name),
+            None,
+            "write the items to the `Formatter` one by one instead",
+        );
+    } else if path.ident.name == sym::write_str
+        && let [arg] = args
+        && match_type(cx, cx.typeck_results().expr_ty(receiver).peel_refs(), &paths::FORMATTER)
+    {
+        let arg = peel_ref_operators(cx, arg);
+        let mut applicability = Applicability::MachineApplicable;
+        let (formatter, _) = snippet_with_context(cx, receiver.span, expr.span.ctxt(), "..", &mut applicability);
+
+        if let Some(macro_call) = root_macro_call(arg.span)
+            && cx.tcx.is_diagnostic_item(sym::format_macro, macro_call.def_id)
+        {
+            let mut inputs_span = None;
+            find_format_args(cx, arg, macro_call.expn, |format_args| {
+                inputs_span = Some(format_args_inputs_span(format_args));
+            });
+            let Some(inputs_span) = inputs_span else { return };
+            let (inputs, _) = snippet_with_context(cx, inputs_span, expr.span.ctxt(), "..", &mut applicability);
+            span_lint_and_sugg(
+                cx,
+                ALLOCATION_IN_FORMAT_IMPL,
+                expr.span,
+                &format!("allocating a `String` with `format!` in `{}` impl", impl_trait.name),
+                "write to the `Formatter` directly",
+                format!("write!({formatter}, {inputs})"),
+                applicability,
+            );
+        } else if let ExprKind::MethodCall(_, value, [], _) = arg.kind
+            && let Some(method_def_id) = cx.typeck_results().type_dependent_def_id(arg.hir_id)
+            && is_diag_trait_item(cx, method_def_id, sym::ToString)
+        {
+            // `ToString` can be implemented without `Display`
+            let value_ty = cx.typeck_results().expr_ty(value);
+            if !cx
+                .tcx
+                .get_diagnostic_item(sym::Display)
+                .is_some_and(|display| implements_trait(cx, value_ty, display, &[]))
+            {
+                applicability = Applicability::MaybeIncorrect;
+            }
+            let (value, _) = snippet_with_context(cx, value.span, expr.span.ctxt(), "..", &mut applicability);
+            span_lint_and_sugg(
+                cx,
+                ALLOCATION_IN_FORMAT_IMPL,
+                expr.span,
+                &format!("allocating a `String` with `to_string` in `{}` impl", impl_trait.name),
+                "write to the `Formatter` directly",
+                format!("write!({formatter}, \"{{}}\", {value})"),
+                applicability,
+            );
+        }
+    }
+}
+
 fn is_format_trait_impl(cx: &LateContext<'_>, impl_item: &ImplItem<'_>) -> Option<FormatTraitNames> {
     if_chain! {
         if impl_item.ident.name == sym::fmt;
@@ -0,0 +1,127 @@
+use clippy_utils::diagnostics::span_lint;
+use clippy_utils::msrvs::Msrv;
+use rustc_attr::StabilityLevel;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::TyCtxt;
+use rustc_semver::RustcVersion;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for uses of standard library items that were stabilized after the MSRV of the
+    /// crate, set by the `rust-version` field of `Cargo.toml`, the `msrv` configuration or the
+    /// `#[clippy::msrv]` attribute.
+    ///
+    /// ### Why is this bad?
+    /// The crate doesn't compile with the Rust versions it claims to support. This is usually
+    /// only noticed by the downstream users of the crate.
+    ///
+    /// ### Known problems
+    /// Only method calls and paths in expressions are checked. Language features, and items used
+    /// in types or imports, are not.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// // rust-version = "1.62"
+    /// let is_even = Some(2).is_some_and(|x| x % 2 == 0);
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// // rust-version = "1.62"
+    /// let is_even = Some(2).map_or(false, |x| x % 2 == 0);
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub INCOMPATIBLE_MSRV,
+    suspicious,
+    "use of a standard library item that is more recent than the MSRV"
+}
+
+pub struct IncompatibleMsrv {
+    msrv: Msrv,
+    stable_since: FxHashMap<DefId, Option<RustcVersion>>,
+}
+
+impl IncompatibleMsrv {
+    #[must_use]
+    pub fn new(msrv: Msrv) -> Self {
+        Self {
+            msrv,
+            stable_since: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the version `def_id` was stabilized in, or that of its closest stable parent.
+    fn stable_since(&mut self, tcx: TyCtxt<'_>, def_id: DefId) -> Option<RustcVersion> {
+        if let Some(&version) = self.stable_since.get(&def_id) {
+            return version;
+        }
+        let version = match tcx.lookup_stability(def_id).map(|stability| stability.level) {
+            Some(StabilityLevel::Stable { since, .. }) => parse_since(since),
+            Some(StabilityLevel::Unstable { .. }) => None,
+            None => tcx.opt_parent(def_id).and_then(|parent| self.stable_since(tcx, parent)),
+        };
+        self.stable_since.insert(def_id, version);
+        version
+    }
+
+    fn check(&mut self, cx: &LateContext<'_>, def_id: DefId, span: Span) {
+        if def_id.is_local() {
+            return;
+        }
+        if let Some(msrv) = self.msrv.current()
+            && let Some(version) = self.stable_since(cx.tcx, def_id)
+            && !msrv.meets(version)
+        {
+            span_lint(
+                cx,
+                INCOMPATIBLE_MSRV,
+                span,
+                &format!(
+                    "`{}` is stable since Rust {version}, but the MSRV of the crate is {msrv}",
+                    cx.tcx.def_path_str(def_id)
+                ),
+            );
+        }
+    }
+}
+
+/// Parses the `since` of a `#[stable]` attribute. Items stabilized in the current version of a
+/// nightly have versions like `1.74.0-nightly`.
+fn parse_since(since: Symbol) -> Option<RustcVersion> {
+    let since = since.as_str();
+    RustcVersion::parse(since.split('-').next().unwrap_or(since)).ok()
+}
+
+impl_lint_pass!(IncompatibleMsrv => [INCOMPATIBLE_MSRV]);
+
+impl<'tcx> LateLintPass<'tcx> for IncompatibleMsrv {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        // Paths created by desugaring `.await`, `?` or `for` aren't written by the user
+        if self.msrv.current().is_none()
+            || in_external_macro(cx.sess(), expr.span)
+            || expr.span.desugaring_kind().is_some()
+        {
+            return;
+        }
+        match expr.kind {
+            ExprKind::MethodCall(segment, ..) => {
+                if let Some(def_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id) {
+                    self.check(cx, def_id, segment.ident.span);
+                }
+            },
+            ExprKind::Path(ref qpath) => {
+                if let Some(def_id) = cx.qpath_res(qpath, expr.hir_id).opt_def_id() {
+                    self.check(cx, def_id, expr.span);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    extract_msrv_attr!(LateContext);
+}
@@ -0,0 +1,307 @@
+use crate::await_holding_invalid::is_mutex_guard;
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::path_to_local;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{walk_expr, Visitor};
+use rustc_hir::{Block, Body, Expr, ExprKind, HirId, MatchSource, PatKind, QPath, Stmt, StmtKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Mutex` and `RwLock` fields or statics which are locked in different orders
+    /// in different places of the crate, i.e. `a` is locked while holding `b` in one place and
+    /// `b` is locked while holding `a` in another one.
+    ///
+    /// ### Why is this bad?
+    /// If both places are executed concurrently, each of them can acquire the first lock and
+    /// then wait forever for the other one to release the second lock.
+    ///
+    /// ### Known problems
+    /// Locks are identified by the field or static they are stored in, so different instances
+    /// of the same type are considered to be the same lock. Locks acquired in called functions
+    /// are not taken into account.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::sync::Mutex;
+    /// struct Bank {
+    ///     accounts: Mutex<Vec<u64>>,
+    ///     log: Mutex<Vec<String>>,
+    /// }
+    ///
+    /// impl Bank {
+    ///     fn deposit(&self, amount: u64) {
+    ///         let mut accounts = self.accounts.lock().unwrap();
+    ///         let mut log = self.log.lock().unwrap();
+    ///         accounts[0] += amount;
+    ///         log.push(format!("deposit {amount}"));
+    ///     }
+    ///
+    ///     fn audit(&self) {
+    ///         let log = self.log.lock().unwrap();
+    ///         let accounts = self.accounts.lock().unwrap();
+    ///         println!("{log:?} {accounts:?}");
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::sync::Mutex;
+    /// # struct Bank {
+    /// #     accounts: Mutex<Vec<u64>>,
+    /// #     log: Mutex<Vec<String>>,
+    /// # }
+    /// impl Bank {
+    ///     // ..
+    ///
+    ///     fn audit(&self) {
+    ///         let accounts = self.accounts.lock().unwrap();
+    ///         let log = self.log.lock().unwrap();
+    ///         println!("{log:?} {accounts:?}");
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub INCONSISTENT_LOCK_ORDER,
+    suspicious,
+    "locking the same pair of locks in different orders"
+}
+
+impl_lint_pass!(InconsistentLockOrder => [INCONSISTENT_LOCK_ORDER]);
+
+#[derive(Default)]
+pub struct InconsistentLockOrder {
+    /// The first place each lock was acquired while holding another one, keyed by the held lock
+    /// and the acquired lock.
+    edges: FxIndexMap<(DefId, DefId), LockEdge>,
+}
+
+struct LockEdge {
+    /// The acquisition of the second lock.
+    hir_id: HirId,
+    span: Span,
+    /// The acquisition of the lock which was held at the time.
+    held_span: Span,
+}
+
+impl<'tcx> LateLintPass<'tcx> for InconsistentLockOrder {
+    fn check_body(&mut self, cx: &LateContext<'tcx>, body: &'tcx Body<'_>) {
+        let mut visitor = LockVisitor {
+            cx,
+            edges: &mut self.edges,
+            held: Vec::new(),
+            stmt_depth: 0,
+            block_depth: 0,
+        };
+        visitor.visit_expr(body.value);
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        for (i, (&(held, acquired), edge)) in self.edges.iter().enumerate() {
+            let Some((j, _, other)) = self.edges.get_full(&(acquired, held)) else {
+                continue;
+            };
+            // Only lint the second edge of each pair.
+            if j > i {
+                continue;
+            }
+            let held_name = lock_name(cx, held);
+            let acquired_name = lock_name(cx, acquired);
+            span_lint_hir_and_then(
+                cx,
+                INCONSISTENT_LOCK_ORDER,
+                edge.hir_id,
+                edge.span,
+                &format!(
+                    "`{acquired_name}` is locked while holding `{held_name}`, which is inconsistent with the lock order used elsewhere"
+                ),
+                |diag| {
+                    diag.span_note(edge.held_span, format!("`{held_name}` is locked here"));
+                    diag.span_note(
+                        other.span,
+                        format!("`{held_name}` is locked while holding `{acquired_name}` here"),
+                    );
+                    diag.help("always acquire these locks in the same order to avoid deadlocks");
+                },
+            );
+        }
+    }
+}
+
+enum Holder {
+    /// A temporary living until the end of the statement at the given depth.
+    Temporary(usize),
+    /// A local declared in the block at the given depth.
+    Local(HirId, usize),
+}
+
+struct HeldLock {
+    lock: DefId,
+    hir_id: HirId,
+    span: Span,
+    holder: Holder,
+}
+
+struct LockVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    edges: &'a mut FxIndexMap<(DefId, DefId), LockEdge>,
+    held: Vec<HeldLock>,
+    stmt_depth: usize,
+    block_depth: usize,
+}
+
+impl<'tcx> LockVisitor<'_, 'tcx> {
+    /// Records the locals holding a guard acquired by the initializer of `let` statements.
+    fn bind_guard(&mut self, binding: HirId, init: &Expr<'_>) {
+        let mut source = init;
+        loop {
+            source = match source.kind {
+                ExprKind::MethodCall(path, receiver, ..)
+                    if matches!(path.ident.as_str(), "unwrap" | "expect" | "unwrap_or_else") =>
+                {
+                    receiver
+                },
+                ExprKind::Match(scrutinee, _, MatchSource::TryDesugar(_))
+                    if let ExprKind::Call(_, [inner]) = scrutinee.kind =>
+                {
+                    inner
+                },
+                _ => break,
+            };
+        }
+        if let Some(held) = self.held.iter_mut().find(|held| held.hir_id == source.hir_id) {
+            held.holder = Holder::Local(binding, self.block_depth);
+        }
+    }
+
+    fn end_stmt(&mut self) {
+        let depth = self.stmt_depth;
+        self.held
+            .retain(|held| !matches!(held.holder, Holder::Temporary(d) if d >= depth));
+        self.stmt_depth -= 1;
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for LockVisitor<'_, 'tcx> {
+    fn visit_block(&mut self, block: &'tcx Block<'tcx>) {
+        self.block_depth += 1;
+        for stmt in block.stmts {
+            self.visit_stmt(stmt);
+        }
+        if let Some(expr) = block.expr {
+            self.stmt_depth += 1;
+            self.visit_expr(expr);
+            self.end_stmt();
+        }
+        let depth = self.block_depth;
+        self.held
+            .retain(|held| !matches!(held.holder, Holder::Local(_, d) if d >= depth));
+        self.block_depth -= 1;
+    }
+
+    fn visit_stmt(&mut self, stmt: &'tcx Stmt<'tcx>) {
+        self.stmt_depth += 1;
+        match stmt.kind {
+            StmtKind::Local(local) => {
+                self.visit_local(local);
+                if let Some(init) = local.init
+                    && let PatKind::Binding(_, binding, ..) = local.pat.kind
+                    && is_guard(self.cx, self.cx.typeck_results().pat_ty(local.pat))
+                {
+                    self.bind_guard(binding, init);
+                }
+            },
+            StmtKind::Expr(expr) | StmtKind::Semi(expr) => {
+                if let ExprKind::Call(func, [arg]) = expr.kind
+                    && let ExprKind::Path(QPath::Resolved(None, path)) = func.kind
+                    && let Res::Def(DefKind::Fn, def_id) = path.res
+                    && self.cx.tcx.is_diagnostic_item(sym::mem_drop, def_id)
+                    && let Some(local) = path_to_local(arg)
+                {
+                    self.held
+                        .retain(|held| !matches!(held.holder, Holder::Local(id, _) if id == local));
+                }
+                self.visit_expr(expr);
+            },
+            StmtKind::Item(_) => {},
+        }
+        self.end_stmt();
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        walk_expr(self, expr);
+
+        if let ExprKind::MethodCall(_, receiver, ..) = expr.kind
+            && !in_external_macro(self.cx.sess(), expr.span)
+            && is_guard(self.cx, self.cx.typeck_results().expr_ty(expr))
+            && let Some(lock) = lock_id(self.cx, receiver)
+        {
+            for held in &self.held {
+                if held.lock != lock {
+                    self.edges.entry((held.lock, lock)).or_insert(LockEdge {
+                        hir_id: expr.hir_id,
+                        span: expr.span,
+                        held_span: held.span,
+                    });
+                }
+            }
+            self.held.push(HeldLock {
+                lock,
+                hir_id: expr.hir_id,
+                span: expr.span,
+                holder: Holder::Temporary(self.stmt_depth),
+            });
+        }
+    }
+}
+
+/// Checks if `ty` is a lock guard, or a `Result` or `Option` containing one.
+fn is_guard<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    let ty::Adt(adt, args) = ty.kind() else {
+        return false;
+    };
+    if is_mutex_guard(cx, adt.did()) {
+        return true;
+    }
+    matches!(cx.tcx.get_diagnostic_name(adt.did()), Some(sym::Result | sym::Option))
+        && args.types().next().map_or(
+            false,
+            |ty| matches!(ty.kind(), ty::Adt(adt, _) if is_mutex_guard(cx, adt.did())),
+        )
+}
+
+/// Gets the field or static the lock `receiver` is stored in.
+fn lock_id(cx: &LateContext<'_>, receiver: &Expr<'_>) -> Option<DefId> {
+    let mut receiver = receiver;
+    while let ExprKind::AddrOf(_, _, inner) | ExprKind::Unary(rustc_hir::UnOp::Deref, inner) = receiver.kind {
+        receiver = inner;
+    }
+    match receiver.kind {
+        ExprKind::Field(base, _) => {
+            let ty::Adt(adt, _) = cx.typeck_results().expr_ty_adjusted(base).peel_refs().kind() else {
+                return None;
+            };
+            let index = cx.typeck_results().opt_field_index(receiver.hir_id)?;
+            adt.is_struct().then(|| adt.non_enum_variant().fields[index].did)
+        },
+        ExprKind::Path(ref qpath) => match cx.qpath_res(qpath, receiver.hir_id) {
+            Res::Def(DefKind::Static(_), def_id) => Some(def_id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn lock_name(cx: &LateContext<'_>, lock: DefId) -> String {
+    if cx.tcx.def_kind(lock) == DefKind::Field {
+        format!("{}::{}", cx.tcx.item_name(cx.tcx.parent(lock)), cx.tcx.item_name(lock))
+    } else {
+        cx.tcx.def_path_str(lock)
+    }
+}
@@ -1,7 +1,7 @@
 //! lint on inherent implementations
 
 use clippy_utils::diagnostics::span_lint_and_note;
-use clippy_utils::is_lint_allowed;
+use clippy_utils::should_skip_lint;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::{Item, ItemKind, Node};
@@ -60,7 +60,7 @@ impl<'tcx> LateLintPass<'tcx> for MultipleInherentImpl {
         for (_, impl_ids) in inherent_impls.into_iter().filter(|(&id, impls)| {
             impls.len() > 1
             // Check for `#[allow]` on the type definition
-            && !is_lint_allowed(
+            && !should_skip_lint(
                 cx,
                 MULTIPLE_INHERENT_IMPL,
                 cx.tcx.hir().local_def_id_to_hir_id(id),
@@ -126,7 +126,7 @@ fn get_impl_span(cx: &LateContext<'_>, id: LocalDefId) -> Option<Span> {
     {
         (!span.from_expansion()
             && impl_item.generics.params.is_empty()
-            && !is_lint_allowed(cx, MULTIPLE_INHERENT_IMPL, id))
+            && !should_skip_lint(cx, MULTIPLE_INHERENT_IMPL, id))
         .then_some(span)
     } else {
         None
@@ -1,6 +1,6 @@
 use clippy_utils::diagnostics::span_lint_and_note;
-use clippy_utils::is_lint_allowed;
 use clippy_utils::macros::root_macro_call_first_node;
+use clippy_utils::should_skip_lint;
 use rustc_ast::LitKind;
 use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
@@ -52,7 +52,7 @@ impl LateLintPass<'_> for LargeIncludeFile {
     fn check_expr(&mut self, cx: &LateContext<'_>, expr: &'_ Expr<'_>) {
         if_chain! {
             if let Some(macro_call) = root_macro_call_first_node(cx, expr);
-            if !is_lint_allowed(cx, LARGE_INCLUDE_FILE, expr.hir_id);
+            if !should_skip_lint(cx, LARGE_INCLUDE_FILE, expr.hir_id);
             if cx.tcx.is_diagnostic_item(sym::include_bytes_macro, macro_call.def_id)
             || cx.tcx.is_diagnostic_item(sym::include_str_macro, macro_call.def_id);
             if let ExprKind::Lit(lit) = &expr.kind;
@@ -1,13 +1,16 @@
-use std::ops::AddAssign;
+use std::ops::{Add, AddAssign};
 
-use clippy_utils::diagnostics::span_lint_and_note;
+use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::fn_has_unsatisfiable_preds;
-use rustc_hir::def_id::LocalDefId;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::intravisit::FnKind;
 use rustc_hir::{Body, FnDecl};
 use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::mir::{self, Local, TerminatorKind, VarDebugInfoContents, RETURN_PLACE};
+use rustc_middle::ty::{self, Instance, InstanceDef, Ty};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
-use rustc_span::Span;
+use rustc_span::{Span, Symbol};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -45,6 +48,12 @@ declare_clippy_lint! {
     /// This lint works by summing up the size of all variables that the user typed, variables that were
     /// implicitly introduced by the compiler for temporaries, function arguments and the return value,
     /// and comparing them against a (configurable, but high-by-default).
+    /// The largest of these are listed in the diagnostic.
+    ///
+    /// With `stack-size-include-callees` enabled, the frames of the functions called through
+    /// direct calls are added as well, following the deepest chain of calls. Only functions of the
+    /// current crate are followed, and recursive calls are ignored. Calls which end up inlined are
+    /// counted too, as the locals of the inlined function are then part of the caller's frame.
     ///
     /// ### Example
     /// This function creates four 500 KB arrays on the stack. Quite big but just small enough to not trigger `large_stack_arrays`.
@@ -80,20 +89,29 @@ declare_clippy_lint! {
 
 pub struct LargeStackFrames {
     maximum_allowed_size: u64,
+    include_callees: bool,
+    /// The stack space used by the deepest chain of calls starting at a function, and the first
+    /// call of that chain. `None` while the function is being visited, to ignore recursion.
+    call_depths: FxHashMap<DefId, Option<(Space, Option<Call>)>>,
 }
 
 impl LargeStackFrames {
     #[must_use]
-    pub fn new(size: u64) -> Self {
+    pub fn new(size: u64, include_callees: bool) -> Self {
         Self {
             maximum_allowed_size: size,
+            include_callees,
+            call_depths: FxHashMap::default(),
         }
     }
 }
 
 impl_lint_pass!(LargeStackFrames => [LARGE_STACK_FRAMES]);
 
-#[derive(Copy, Clone)]
+/// The number of locals listed in the diagnostic, among the ones using at least 10% of the frame.
+const LISTED_LOCALS: usize = 5;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 enum Space {
     Used(u64),
     Overflow,
@@ -119,6 +137,101 @@ impl AddAssign<u64> for Space {
     }
 }
 
+impl Add for Space {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        match rhs {
+            Self::Used(rhs) => self += rhs,
+            Self::Overflow => self = Self::Overflow,
+        }
+        self
+    }
+}
+
+impl std::fmt::Display for Space {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Used(used) => write!(f, "{used} bytes"),
+            Self::Overflow => f.write_str("more than `u64::MAX` bytes"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Call {
+    callee: DefId,
+    span: Span,
+}
+
+/// The locals of a function's stack frame, with their sizes.
+struct Frame<'tcx> {
+    size: Space,
+    locals: Vec<(Local, Ty<'tcx>, u64)>,
+}
+
+fn frame_of<'tcx>(cx: &LateContext<'tcx>, def_id: DefId, mir: &mir::Body<'tcx>) -> Frame<'tcx> {
+    let param_env = cx.tcx.param_env(def_id);
+    let mut frame = Frame {
+        size: Space::Used(0),
+        locals: Vec::new(),
+    };
+
+    for (local, decl) in mir.local_decls.iter_enumerated() {
+        if let Ok(layout) = cx.tcx.layout_of(param_env.and(decl.ty)) {
+            frame.size += layout.size.bytes();
+            frame.locals.push((local, decl.ty, layout.size.bytes()));
+        }
+    }
+
+    frame
+}
+
+impl LargeStackFrames {
+    /// Returns the stack space used by the deepest chain of direct calls from `def_id`.
+    fn callees_depth<'tcx>(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        def_id: DefId,
+        mir: &mir::Body<'tcx>,
+    ) -> (Space, Option<Call>) {
+        let param_env = cx.tcx.param_env(def_id);
+        let mut deepest = (Space::Used(0), None);
+
+        for block in mir.basic_blocks.iter() {
+            if let TerminatorKind::Call { func, fn_span, .. } = &block.terminator().kind
+                && let ty::FnDef(callee, args) = *func.ty(mir, cx.tcx).kind()
+                && let Ok(Some(instance)) = Instance::resolve(cx.tcx, param_env, callee, args)
+                && let InstanceDef::Item(callee) = instance.def
+                && let Some(local_callee) = callee.as_local()
+                && cx.tcx.hir().maybe_body_owned_by(local_callee).is_some()
+                && !fn_has_unsatisfiable_preds(cx, callee)
+                && let Some(depth) = self.stack_depth(cx, callee)
+                && depth > deepest.0
+            {
+                deepest = (depth, Some(Call { callee, span: *fn_span }));
+            }
+        }
+
+        deepest
+    }
+
+    /// Returns the stack space used by `def_id` and the functions it calls, `None` if it is
+    /// already being visited.
+    fn stack_depth(&mut self, cx: &LateContext<'_>, def_id: DefId) -> Option<Space> {
+        if let Some(depth) = self.call_depths.get(&def_id) {
+            return depth.map(|(depth, _)| depth);
+        }
+        self.call_depths.insert(def_id, None);
+
+        let mir = cx.tcx.optimized_mir(def_id);
+        let (callees, call) = self.callees_depth(cx, def_id, mir);
+        let depth = frame_of(cx, def_id, mir).size + callees;
+        self.call_depths.insert(def_id, Some((depth, call)));
+        Some(depth)
+    }
+}
+
 impl<'tcx> LateLintPass<'tcx> for LargeStackFrames {
     fn check_fn(
         &mut self,
@@ -136,25 +249,78 @@ impl<'tcx> LateLintPass<'tcx> for LargeStackFrames {
         }
 
         let mir = cx.tcx.optimized_mir(def_id);
-        let param_env = cx.tcx.param_env(def_id);
-
-        let mut frame_size = Space::Used(0);
-
-        for local in &mir.local_decls {
-            if let Ok(layout) = cx.tcx.layout_of(param_env.and(local.ty)) {
-                frame_size += layout.size.bytes();
-            }
-        }
+        let mut frame = frame_of(cx, def_id, mir);
+        let (callees, call) = if self.include_callees {
+            self.call_depths.insert(def_id, None);
+            let (callees, call) = self.callees_depth(cx, def_id, mir);
+            self.call_depths.insert(def_id, Some((frame.size + callees, call)));
+            (callees, call)
+        } else {
+            (Space::Used(0), None)
+        };
 
-        if frame_size.exceeds_limit(self.maximum_allowed_size) {
-            span_lint_and_note(
+        if (frame.size + callees).exceeds_limit(self.maximum_allowed_size) {
+            span_lint_and_then(
                 cx,
                 LARGE_STACK_FRAMES,
                 span,
                 "this function allocates a large amount of stack space",
-                None,
-                "allocating large amounts of stack space can overflow the stack",
+                |diag| {
+                    diag.note("allocating large amounts of stack space can overflow the stack");
+
+                    let names = local_names(mir);
+                    frame.locals.sort_by(|(_, _, size), (_, _, other)| other.cmp(size));
+                    for &(local, ty, size) in frame
+                        .locals
+                        .iter()
+                        .filter(|(.., size)| *size > 0 && frame.size <= Space::Used(size.saturating_mul(10)))
+                        .take(LISTED_LOCALS)
+                    {
+                        let span = mir.local_decls[local].source_info.span;
+                        let local = if local == RETURN_PLACE {
+                            "the return value".to_string()
+                        } else if let Some(name) = names.get(&local) {
+                            format!("`{name}`")
+                        } else {
+                            "a temporary".to_string()
+                        };
+                        diag.span_note(span, format!("{local} of type `{ty}` uses {size} bytes"));
+                    }
+
+                    if let Some(call) = call {
+                        let mut chain = vec![cx.tcx.def_path_str(call.callee)];
+                        let mut visited = FxHashSet::default();
+                        let mut next = call.callee;
+                        while visited.insert(next)
+                            && let Some(Some((_, Some(call)))) = self.call_depths.get(&next)
+                        {
+                            chain.push(cx.tcx.def_path_str(call.callee));
+                            next = call.callee;
+                        }
+                        diag.span_note(
+                            call.span,
+                            format!(
+                                "the calls to {} use {callees} more",
+                                chain
+                                    .iter()
+                                    .map(|name| format!("`{name}`"))
+                                    .collect::<Vec<_>>()
+                                    .join(" -> ")
+                            ),
+                        );
+                    }
+                },
             );
         }
     }
 }
+
+fn local_names(mir: &mir::Body<'_>) -> FxHashMap<Local, Symbol> {
+    mir.var_debug_info
+        .iter()
+        .filter_map(|info| match info.value {
+            VarDebugInfoContents::Place(place) => Some((place.as_local()?, info.name)),
+            VarDebugInfoContents::Const(_) => None,
+        })
+        .collect()
+}
@@ -24,6 +24,7 @@
 extern crate rustc_arena;
 extern crate rustc_ast;
 extern crate rustc_ast_pretty;
+extern crate rustc_attr;
 extern crate rustc_data_structures;
 extern crate rustc_driver;
 extern crate rustc_errors;
@@ -61,6 +62,8 @@ pub mod deprecated_lints;
 #[cfg_attr(feature = "internal", allow(clippy::missing_clippy_version_attribute))]
 mod utils;
 
+pub mod plugin;
+
 mod declared_lints;
 mod renamed_lints;
 
@@ -107,6 +110,7 @@ mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
 mod disallowed_script_idents;
+mod disallowed_suppressions;
 mod disallowed_types;
 mod doc;
 mod double_parens;
@@ -153,6 +157,8 @@ mod implicit_return;
 mod implicit_saturating_add;
 mod implicit_saturating_sub;
 mod implied_bounds_in_impls;
+mod incompatible_msrv;
+mod inconsistent_lock_order;
 mod inconsistent_struct_constructor;
 mod index_refutable_slice;
 mod indexing_slicing;
@@ -215,6 +221,7 @@ mod missing_doc;
 mod missing_enforced_import_rename;
 mod missing_fields_in_debug;
 mod missing_inline;
+mod missing_send_sync_bounds;
 mod missing_trait_methods;
 mod mixed_read_write_in_expression;
 mod module_style;
@@ -270,6 +277,7 @@ mod pub_use;
 mod question_mark;
 mod question_mark_used;
 mod ranges;
+mod raw_ownership;
 mod raw_strings;
 mod rc_clone_in_vec_init;
 mod read_zero_byte_vec;
@@ -295,6 +303,7 @@ mod self_named_constructors;
 mod semicolon_block;
 mod semicolon_if_nothing_returned;
 mod serde_api;
+mod serde_attributes;
 mod shadow;
 mod significant_drop_tightening;
 mod single_call_fn;
@@ -304,6 +313,7 @@ mod single_range_in_vec_init;
 mod size_of_in_element_count;
 mod size_of_ref;
 mod slow_vector_initialization;
+mod stale_allows;
 mod std_instead_of_core;
 mod strings;
 mod strlen_on_c_strings;
@@ -329,6 +339,7 @@ mod unit_return_expecting_ord;
 mod unit_types;
 mod unnamed_address;
 mod unnecessary_box_returns;
+mod unnecessary_lock;
 mod unnecessary_owned_empty_strings;
 mod unnecessary_self_imports;
 mod unnecessary_struct_initialization;
@@ -359,6 +370,7 @@ use crate::utils::conf::metadata::get_configuration_metadata;
 use crate::utils::conf::TryConf;
 pub use crate::utils::conf::{lookup_conf_file, Conf};
 use crate::utils::FindAll;
+pub use clippy_utils::diagnostics::emit_only_on_changed_lines;
 
 /// Register all pre expansion lints
 ///
@@ -483,6 +495,22 @@ impl LintCategory {
         matches!(self, Correctness | Suspicious | Style | Complexity | Perf)
     }
 
+    fn name(self) -> &'static str {
+        match self {
+            Cargo => "cargo",
+            Complexity => "complexity",
+            Correctness => "correctness",
+            Nursery => "nursery",
+            Pedantic => "pedantic",
+            Perf => "perf",
+            Restriction => "restriction",
+            Style => "style",
+            Suspicious => "suspicious",
+            #[cfg(feature = "internal")]
+            Internal => "internal",
+        }
+    }
+
     fn group(self, groups: &mut RegistrationGroups) -> &mut Vec<LintId> {
         match self {
             Cargo => &mut groups.cargo,
@@ -531,6 +559,20 @@ pub fn explain(name: &str) -> i32 {
     }
 }
 
+/// Returns the group of a Clippy lint, e.g. `style` for `clippy::needless_return`.
+pub fn lint_group(name: &str) -> Option<&'static str> {
+    let target = format!("clippy::{}", name.strip_prefix("clippy::")?.to_ascii_uppercase());
+    declared_lints::LINTS
+        .iter()
+        .find(|info| info.lint.name == target)
+        .map(|info| info.category.name())
+}
+
+/// Checks if `name` is a Clippy lint group, e.g. `correctness`.
+pub fn is_lint_group(name: &str) -> bool {
+    declared_lints::LINTS.iter().any(|info| info.category.name() == name)
+}
+
 fn register_categories(store: &mut rustc_lint::LintStore) {
     let mut groups = RegistrationGroups::default();
 
@@ -611,6 +653,21 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_early_pass(|| Box::new(utils::format_args_collector::FormatArgsCollector));
     store.register_late_pass(|_| Box::new(utils::dump_hir::DumpHir));
     store.register_late_pass(|_| Box::new(utils::author::Author));
+    register_unsafe_inventory(store);
+    if let Ok(suppressions_output) = std::env::var("CLIPPY_SUPPRESSIONS") {
+        store
+            .register_late_pass(move |_| Box::new(utils::suppressions::Suppressions::new(suppressions_output.clone())));
+    }
+    if let Ok(type_sizes_output) = std::env::var("CLIPPY_TYPE_SIZES") {
+        let enum_variant_size_threshold = conf.enum_variant_size_threshold;
+        store.register_late_pass(move |_| {
+            Box::new(utils::type_sizes::TypeSizes::new(
+                type_sizes_output.clone(),
+                enum_variant_size_threshold,
+            ))
+        });
+    }
+    utils::custom_lints::register(store, sess, &conf.custom_lints);
     let await_holding_invalid_types = conf.await_holding_invalid_types.clone();
     store.register_late_pass(move |_| {
         Box::new(await_holding_invalid::AwaitHolding::new(
@@ -731,7 +788,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::new(drop_forget_ref::DropForgetRef));
     store.register_late_pass(|_| Box::new(empty_enum::EmptyEnum));
     store.register_late_pass(|_| Box::new(invalid_upcast_comparisons::InvalidUpcastComparisons));
-    store.register_late_pass(|_| Box::<regex::Regex>::default());
+    let regex_constructors = conf.regex_constructors.clone();
+    store.register_late_pass(move |_| Box::new(regex::Regex::new(regex_constructors.clone())));
     let ignore_interior_mutability = conf.ignore_interior_mutability.clone();
     store.register_late_pass(move |_| Box::new(copies::CopyAndPaste::new(ignore_interior_mutability.clone())));
     store.register_late_pass(|_| Box::new(copy_iterator::CopyIterator));
@@ -906,6 +964,12 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(move |_| Box::new(disallowed_macros::DisallowedMacros::new(disallowed_macros.clone())));
     let disallowed_methods = conf.disallowed_methods.clone();
     store.register_late_pass(move |_| Box::new(disallowed_methods::DisallowedMethods::new(disallowed_methods.clone())));
+    let disallowed_suppressions = conf.disallowed_suppressions.clone();
+    store.register_late_pass(move |_| {
+        Box::new(disallowed_suppressions::DisallowedSuppressions::new(
+            disallowed_suppressions.clone(),
+        ))
+    });
     store.register_early_pass(|| Box::new(asm_syntax::InlineAsmX86AttSyntax));
     store.register_early_pass(|| Box::new(asm_syntax::InlineAsmX86IntelSyntax));
     store.register_late_pass(|_| Box::new(empty_drop::EmptyDrop));
@@ -948,7 +1012,14 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         ))
     });
     let allow_mixed_uninlined = conf.allow_mixed_uninlined_format_args;
-    store.register_late_pass(move |_| Box::new(format_args::FormatArgs::new(msrv(), allow_mixed_uninlined)));
+    let format_like_macros = conf.format_like_macros.clone();
+    store.register_late_pass(move |_| {
+        Box::new(format_args::FormatArgs::new(
+            msrv(),
+            allow_mixed_uninlined,
+            format_like_macros.clone(),
+        ))
+    });
     store.register_late_pass(|_| Box::new(trailing_empty_array::TrailingEmptyArray));
     store.register_early_pass(|| Box::new(octal_escapes::OctalEscapes));
     store.register_late_pass(|_| Box::new(needless_late_init::NeedlessLateInit));
@@ -963,9 +1034,11 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     let allow_print_in_tests = conf.allow_print_in_tests;
     store.register_late_pass(move |_| Box::new(write::Write::new(allow_print_in_tests)));
     let cargo_ignore_publish = conf.cargo_ignore_publish;
+    let allowed_duplicate_crates = conf.allowed_duplicate_crates.clone();
     store.register_late_pass(move |_| {
         Box::new(cargo::Cargo {
             ignore_publish: cargo_ignore_publish,
+            allowed_duplicate_crates: allowed_duplicate_crates.clone(),
         })
     });
     store.register_early_pass(|| Box::new(crate_in_macro_def::CrateInMacroDef));
@@ -1063,7 +1136,13 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
         })
     });
     let stack_size_threshold = conf.stack_size_threshold;
-    store.register_late_pass(move |_| Box::new(large_stack_frames::LargeStackFrames::new(stack_size_threshold)));
+    let stack_size_include_callees = conf.stack_size_include_callees;
+    store.register_late_pass(move |_| {
+        Box::new(large_stack_frames::LargeStackFrames::new(
+            stack_size_threshold,
+            stack_size_include_callees,
+        ))
+    });
     store.register_late_pass(|_| Box::new(single_range_in_vec_init::SingleRangeInVecInit));
     store.register_late_pass(move |_| {
         Box::new(needless_pass_by_ref_mut::NeedlessPassByRefMut::new(
@@ -1102,7 +1181,28 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::<reserve_after_initialization::ReserveAfterInitialization>::default());
     store.register_late_pass(|_| Box::new(implied_bounds_in_impls::ImpliedBoundsInImpls));
     store.register_late_pass(|_| Box::new(missing_asserts_for_indexing::MissingAssertsForIndexing));
+    store.register_late_pass(|_| Box::new(missing_send_sync_bounds::MissingSendSyncBounds));
+    store.register_late_pass(|_| Box::new(raw_ownership::RawOwnership));
+    store.register_late_pass(|_| Box::<inconsistent_lock_order::InconsistentLockOrder>::default());
+    store.register_late_pass(|_| Box::new(serde_attributes::SerdeAttributes));
+    store.register_late_pass(|_| Box::<unnecessary_lock::UnnecessaryLock>::default());
+    store.register_late_pass(move |_| Box::new(incompatible_msrv::IncompatibleMsrv::new(msrv())));
     // add lints here, do not remove this comment, it's used in `new_lint`
+    // Registered last, so that the lints emitted by the other passes are known when it runs
+    store.register_late_pass(|_| Box::<stale_allows::StaleAllows>::default());
+}
+
+/// Registers the pass writing the inventory of `unsafe` code if `CLIPPY_UNSAFE_INVENTORY` is set.
+///
+/// Used on its own for dependencies, whose lints are capped to `allow`.
+pub fn register_unsafe_inventory(store: &mut rustc_lint::LintStore) {
+    if let Ok(unsafe_inventory_output) = std::env::var("CLIPPY_UNSAFE_INVENTORY") {
+        store.register_late_pass(move |_| {
+            Box::new(utils::unsafe_inventory::UnsafeInventory::new(
+                unsafe_inventory_output.clone(),
+            ))
+        });
+    }
 }
 
 #[rustfmt::skip]
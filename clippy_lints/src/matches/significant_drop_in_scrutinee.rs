@@ -1,7 +1,7 @@
 use crate::FxHashSet;
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::source::{indent_of, snippet};
-use clippy_utils::{get_attr, is_lint_allowed};
+use clippy_utils::{get_attr, should_skip_lint};
 use rustc_errors::{Applicability, Diagnostic};
 use rustc_hir::intravisit::{walk_expr, Visitor};
 use rustc_hir::{Arm, Expr, ExprKind, MatchSource};
@@ -18,7 +18,7 @@ pub(super) fn check<'tcx>(
     arms: &'tcx [Arm<'_>],
     source: MatchSource,
 ) {
-    if is_lint_allowed(cx, SIGNIFICANT_DROP_IN_SCRUTINEE, expr.hir_id) {
+    if should_skip_lint(cx, SIGNIFICANT_DROP_IN_SCRUTINEE, expr.hir_id) {
         return;
     }
 
@@ -0,0 +1,135 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::implements_trait;
+use rustc_ast::ImplPolarity;
+use rustc_hir::{Item, ItemKind, TraitRef, Unsafety};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, GenericArgKind, GenericParamDefKind, Ty};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `unsafe impl Send` and `unsafe impl Sync` on generic types which
+    /// don't require the corresponding bound on the type parameters stored in the type.
+    ///
+    /// ### Why is this bad?
+    /// Such an implementation allows sending or sharing any `T` across threads through
+    /// the type, including `Rc` or `Cell`, which can lead to data races. This is one of
+    /// the most common soundness bugs found in unsafe code.
+    ///
+    /// ### Known problems
+    /// Types which only give access to `T` through a lock, like `Mutex<T>`, only need
+    /// `T: Send` to implement `Sync`. Their implementations are linted anyway, as the lint
+    /// can't tell them apart from types giving out `&T`, which need `T: Sync`.
+    ///
+    /// Type parameters only used in `PhantomData` behind a raw pointer or a function
+    /// pointer (e.g. `PhantomData<*const T>` or `PhantomData<fn() -> T>`) are ignored,
+    /// as those are commonly used as markers. Types which don't store a `T` but still
+    /// hand one out, e.g. through a raw pointer stored as `usize`, can't be detected.
+    ///
+    /// ### Example
+    /// ```rust
+    /// struct MyBox<T> {
+    ///     ptr: *mut T,
+    /// }
+    ///
+    /// unsafe impl<T> Send for MyBox<T> {}
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// struct MyBox<T> {
+    ///     ptr: *mut T,
+    /// }
+    ///
+    /// unsafe impl<T: Send> Send for MyBox<T> {}
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub MISSING_SEND_SYNC_BOUNDS,
+    nursery,
+    "`unsafe impl Send` or `unsafe impl Sync` without bounds on the type parameters"
+}
+declare_lint_pass!(MissingSendSyncBounds => [MISSING_SEND_SYNC_BOUNDS]);
+
+impl<'tcx> LateLintPass<'tcx> for MissingSendSyncBounds {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Impl(hir_impl) = item.kind
+            && hir_impl.unsafety == Unsafety::Unsafe
+            && hir_impl.polarity == ImplPolarity::Positive
+            && !in_external_macro(cx.sess(), item.span)
+            && let Some(trait_id) = hir_impl.of_trait.as_ref().and_then(TraitRef::trait_def_id)
+            && let Some(send_trait) = cx.tcx.get_diagnostic_item(sym::Send)
+            && let Some(sync_trait) = cx.tcx.get_diagnostic_item(sym::Sync)
+            && let Some((trait_name, bound)) = if trait_id == send_trait {
+                Some(("Send", send_trait))
+            } else if trait_id == sync_trait {
+                Some(("Sync", sync_trait))
+            } else {
+                None
+            }
+            && let Some(trait_ref) = cx.tcx.impl_trait_ref(item.owner_id)
+            && let self_ty = trait_ref.instantiate_identity().self_ty()
+            && let ty::Adt(adt_def, args) = self_ty.kind()
+        {
+            let field_tys: Vec<_> = adt_def.all_fields().map(|field| field.ty(cx.tcx, args)).collect();
+            let missing_bounds: Vec<_> = cx
+                .tcx
+                .generics_of(item.owner_id)
+                .params
+                .iter()
+                .filter(|param| matches!(param.kind, GenericParamDefKind::Type { .. }))
+                .map(|param| cx.tcx.mk_param_from_def(param).expect_ty())
+                .filter(|&param_ty| !implements_trait(cx, param_ty, bound, &[]))
+                .filter(|&param_ty| field_tys.iter().any(|&field_ty| stores_param(cx, field_ty, param_ty)))
+                .map(|param_ty| format!("{param_ty}: {trait_name}"))
+                .collect();
+
+            if !missing_bounds.is_empty() {
+                let source_map = cx.sess().source_map();
+                let span = if source_map.is_multiline(item.span) {
+                    source_map.span_until_char(item.span, '\n')
+                } else {
+                    item.span
+                };
+                let bounds = missing_bounds.join(", ");
+
+                span_lint_and_help(
+                    cx,
+                    MISSING_SEND_SYNC_BOUNDS,
+                    span,
+                    &format!("`unsafe impl {trait_name}` for `{self_ty}` does not require `{bounds}`"),
+                    None,
+                    &format!("consider adding `{bounds}` to the bounds of the impl"),
+                );
+            }
+        }
+    }
+}
+
+/// Checks if a field of type `ty` stores a value of the type parameter `param_ty`, or gives
+/// access to one.
+fn stores_param<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, param_ty: Ty<'tcx>) -> bool {
+    match ty.kind() {
+        ty::Adt(adt_def, args) if Some(adt_def.did()) == cx.tcx.lang_items().phantom_data() => {
+            args.types().any(|marker_ty| phantom_owns_param(marker_ty, param_ty))
+        },
+        ty::Adt(_, args) => args.types().any(|arg_ty| stores_param(cx, arg_ty, param_ty)),
+        _ => ty.walk().any(|arg| arg.unpack() == GenericArgKind::Type(param_ty)),
+    }
+}
+
+/// Checks if `PhantomData<ty>` marks ownership of the type parameter `param_ty`. Raw pointers and
+/// function pointers are only used as markers for variance and auto traits.
+fn phantom_owns_param<'tcx>(ty: Ty<'tcx>, param_ty: Ty<'tcx>) -> bool {
+    let mut walker = ty.walk();
+    while let Some(arg) = walker.next() {
+        if let GenericArgKind::Type(inner_ty) = arg.unpack() {
+            if matches!(inner_ty.kind(), ty::RawPtr(_) | ty::FnPtr(_)) {
+                walker.skip_current_subtree();
+            } else if inner_ty == param_ty {
+                return true;
+            }
+        }
+    }
+    false
+}
@@ -1,6 +1,6 @@
 use clippy_utils::diagnostics::span_lint_and_help;
-use clippy_utils::is_lint_allowed;
 use clippy_utils::macros::span_is_local;
+use clippy_utils::should_skip_lint;
 use rustc_hir::def_id::DefIdMap;
 use rustc_hir::{Impl, Item, ItemKind};
 use rustc_lint::{LateContext, LateLintPass};
@@ -59,7 +59,7 @@ declare_lint_pass!(MissingTraitMethods => [MISSING_TRAIT_METHODS]);
 
 impl<'tcx> LateLintPass<'tcx> for MissingTraitMethods {
     fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
-        if !is_lint_allowed(cx, MISSING_TRAIT_METHODS, item.hir_id())
+        if !should_skip_lint(cx, MISSING_TRAIT_METHODS, item.hir_id())
             && span_is_local(item.span)
             && let ItemKind::Impl(Impl {
                 items,
@@ -88,7 +88,7 @@ impl<'tcx> LateLintPass<'tcx> for MultipleUnsafeOpsPerBlock {
     }
 }
 
-fn collect_unsafe_exprs<'tcx>(
+pub(crate) fn collect_unsafe_exprs<'tcx>(
     cx: &LateContext<'tcx>,
     node: impl Visitable<'tcx>,
     unsafe_ops: &mut Vec<(&'static str, Span)>,
@@ -0,0 +1,83 @@
+//! The interface used by lint plugins loaded into `clippy-driver`.
+//!
+//! A plugin is a `dylib` crate depending on the same revision of `clippy_lints` (and usually
+//! `clippy_utils`) as the driver loading it, built with the same toolchain. It declares its
+//! registration function with [`declare_clippy_plugin!`], which is called once per compilation
+//! session after Clippy's own lints have been registered:
+//!
+//! ```ignore
+//! #![feature(rustc_private)]
+//!
+//! extern crate rustc_lint;
+//! extern crate rustc_session;
+//!
+//! clippy_lints::declare_clippy_plugin!(register);
+//!
+//! fn register(registry: &mut clippy_lints::plugin::Registry<'_>) {
+//!     registry.lint_store.register_lints(&[&QUERY_CONSTRUCTION]);
+//!     registry.lint_store.register_late_pass(|_| Box::new(QueryConstruction));
+//! }
+//! ```
+//!
+//! Plugins are listed in the `plugins` configuration option or in the `CLIPPY_PLUGINS`
+//! environment variable.
+//!
+//! A plugin links its own copies of `clippy_lints` and `clippy_utils`, including their statics.
+//! The lints of a plugin are therefore not restricted by `--only-changed`, and `stale_allows`
+//! doesn't see the `#[allow]`s suppressing them.
+
+#![allow(clippy::module_name_repetitions)]
+
+use crate::utils::conf::Conf;
+use rustc_lint::LintStore;
+use rustc_session::Session;
+
+/// The version of the plugin interface, increased whenever [`Registry`] or [`Declaration`]
+/// change.
+pub const API_VERSION: u32 = 3;
+
+/// A hash of the sources of `clippy_lints` and `clippy_utils` the plugin was built against.
+/// Plugins built against other sources are rejected, as they could see a different [`Conf`].
+pub const CLIPPY_LINTS_FINGERPRINT: &str = env!("CLIPPY_LINTS_FINGERPRINT");
+
+/// The commit of the compiler the plugin was built with. The layout of the `rustc` types passed
+/// to the plugin is only the same when both were built with the same compiler. `unknown` if it
+/// couldn't be found, in which case no plugin is loaded.
+pub const RUSTC_COMMIT_HASH: &str = env!("RUSTC_COMMIT_HASH");
+
+/// The name of the symbol exported by [`declare_clippy_plugin!`] holding the API version.
+pub const API_VERSION_SYMBOL: &[u8] = b"CLIPPY_PLUGIN_API_VERSION";
+
+/// The name of the symbol exported by [`declare_clippy_plugin!`] holding the
+/// [`Declaration`].
+pub const DECLARATION_SYMBOL: &[u8] = b"CLIPPY_PLUGIN_DECLARATION";
+
+/// Everything a plugin can access when registering its lints.
+pub struct Registry<'a> {
+    pub sess: &'a Session,
+    pub lint_store: &'a mut LintStore,
+    pub conf: &'a Conf,
+}
+
+/// Exported by plugins through [`declare_clippy_plugin!`].
+pub struct Declaration {
+    pub rustc_commit_hash: &'static str,
+    pub clippy_lints_fingerprint: &'static str,
+    pub register: fn(&mut Registry<'_>),
+}
+
+/// Exports the registration function of a lint plugin.
+#[macro_export]
+macro_rules! declare_clippy_plugin {
+    ($register:path) => {
+        #[no_mangle]
+        pub static CLIPPY_PLUGIN_API_VERSION: u32 = $crate::plugin::API_VERSION;
+
+        #[no_mangle]
+        pub static CLIPPY_PLUGIN_DECLARATION: $crate::plugin::Declaration = $crate::plugin::Declaration {
+            rustc_commit_hash: $crate::plugin::RUSTC_COMMIT_HASH,
+            clippy_lints_fingerprint: $crate::plugin::CLIPPY_LINTS_FINGERPRINT,
+            register: $register,
+        };
+    };
+}
@@ -0,0 +1,471 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::mir::local_assignments;
+use clippy_utils::ty::is_c_void;
+use clippy_utils::visitors::contains_unsafe_block;
+use clippy_utils::{fn_has_unsatisfiable_preds, match_def_path, paths};
+use rustc_ast::Mutability;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, FnDecl, Unsafety};
+use rustc_index::bit_set::BitSet;
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::mir::{
+    self, BasicBlock, BorrowKind, Local, LocalKind, Location, Operand, Place, Rvalue, StatementKind, TerminatorKind,
+};
+use rustc_middle::ty::{self, AdtDef};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::{sym, Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for functions which take ownership of the same raw pointer twice, using
+    /// `Box::from_raw`, `Vec::from_raw_parts` or the equivalent functions of `String` and
+    /// `CString`.
+    ///
+    /// ### Why is this bad?
+    /// Both of the resulting values own the same allocation, so it will be freed twice once
+    /// they are dropped. Any use of the first value after the second one is dropped is a
+    /// use-after-free.
+    ///
+    /// ### Known problems
+    /// Only pointers which aren't reassigned between both calls are detected. Calls where the
+    /// first value is given up again, e.g. using `Box::into_raw` or `mem::forget`, are ignored.
+    ///
+    /// `Rc::from_raw` and `Arc::from_raw` are ignored, as taking ownership twice is fine if the
+    /// reference count was incremented. Pointers to `c_void` are ignored as well, as
+    /// `from_raw_with_void_ptr` already lints them.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// let ptr = Box::into_raw(Box::new(String::from("hello")));
+    /// let first = unsafe { Box::from_raw(ptr) };
+    /// let second = unsafe { Box::from_raw(ptr) };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// let ptr = Box::into_raw(Box::new(String::from("hello")));
+    /// let first = unsafe { Box::from_raw(ptr) };
+    /// let second = first.clone();
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub DOUBLE_FROM_RAW,
+    suspicious,
+    "taking ownership of the same raw pointer twice"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `ptr::read` (and its relatives) on a local variable which is dropped again
+    /// afterwards, either explicitly or at the end of its scope.
+    ///
+    /// ### Why is this bad?
+    /// `ptr::read` creates a bitwise copy of the value without moving it out of the variable.
+    /// Unless the original is forgotten, both copies are dropped, which usually results in a
+    /// double free.
+    ///
+    /// ### Known problems
+    /// Paths on which the variable is mutably borrowed after being read are ignored, as the
+    /// value may have been replaced using `ptr::write`.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// let v = vec![1, 2, 3];
+    /// let copy = unsafe { std::ptr::read(&v) };
+    /// drop(copy);
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// let v = vec![1, 2, 3];
+    /// let copy = unsafe { std::ptr::read(&v) };
+    /// std::mem::forget(v);
+    /// drop(copy);
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub DROP_AFTER_PTR_READ,
+    suspicious,
+    "dropping a value after duplicating it with `ptr::read`"
+}
+
+declare_lint_pass!(RawOwnership => [DOUBLE_FROM_RAW, DROP_AFTER_PTR_READ]);
+
+impl<'tcx> LateLintPass<'tcx> for RawOwnership {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        _: Span,
+        def_id: LocalDefId,
+    ) {
+        let is_unsafe_fn = match kind {
+            FnKind::ItemFn(_, _, header) => header.unsafety == Unsafety::Unsafe,
+            FnKind::Method(_, sig) => sig.header.unsafety == Unsafety::Unsafe,
+            FnKind::Closure => false,
+        };
+        // Building MIR is expensive, all of the functions we're looking for are `unsafe`.
+        // Building MIR for `fn`s with unsatisfiable preds results in ICE.
+        if !(is_unsafe_fn || contains_unsafe_block(cx, body.value))
+            || fn_has_unsatisfiable_preds(cx, def_id.to_def_id())
+        {
+            return;
+        }
+
+        let mir = cx.tcx.optimized_mir(def_id.to_def_id());
+        let calls: Vec<_> = mir
+            .basic_blocks
+            .iter_enumerated()
+            .filter(|(_, bbdata)| !bbdata.is_cleanup)
+            .filter_map(|(bb, bbdata)| {
+                let terminator = bbdata.terminator();
+                if terminator.source_info.span.from_expansion() {
+                    return None;
+                }
+                if let TerminatorKind::Call {
+                    func,
+                    args,
+                    destination,
+                    target: Some(target),
+                    ..
+                } = &terminator.kind
+                    && let ty::FnDef(fn_id, _) = *func.ty(mir, cx.tcx).kind()
+                    && let Some(kind) = RawCall::classify(cx, fn_id)
+                    && let Some(Operand::Copy(arg) | Operand::Move(arg)) = args.first()
+                {
+                    Some(Call {
+                        kind,
+                        block: bb,
+                        target: *target,
+                        arg: *arg,
+                        destination: *destination,
+                        span: terminator.source_info.span,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        check_double_from_raw(cx, mir, &calls);
+        check_drop_after_ptr_read(cx, mir, &calls);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RawCall {
+    /// `Box::from_raw` and friends, with the name of the type and the function.
+    FromRaw(Symbol, Symbol),
+    /// `ptr::read` and friends.
+    PtrRead,
+    /// `mem::drop`.
+    Drop,
+    /// Functions giving up ownership of their argument without dropping it, e.g. `mem::forget`
+    /// and `Box::into_raw`.
+    Leak,
+}
+
+impl RawCall {
+    fn classify(cx: &LateContext<'_>, fn_id: DefId) -> Option<Self> {
+        if match_def_path(cx, fn_id, &paths::PTR_READ)
+            || match_def_path(cx, fn_id, &paths::PTR_READ_UNALIGNED)
+            || match_def_path(cx, fn_id, &paths::PTR_READ_VOLATILE)
+        {
+            return Some(Self::PtrRead);
+        }
+        match cx.tcx.get_diagnostic_name(fn_id) {
+            Some(sym::mem_drop) => return Some(Self::Drop),
+            Some(sym::mem_forget) => return Some(Self::Leak),
+            _ => {},
+        }
+
+        let impl_id = cx.tcx.impl_of_method(fn_id)?;
+        if cx.tcx.trait_id_of_impl(impl_id).is_some() {
+            return None;
+        }
+        let name = cx.tcx.item_name(fn_id);
+        match cx.tcx.type_of(impl_id).instantiate_identity().kind() {
+            ty::RawPtr(_) if matches!(name.as_str(), "read" | "read_unaligned" | "read_volatile") => {
+                Some(Self::PtrRead)
+            },
+            ty::Adt(adt, _) if adt.is_manually_drop() && name == sym::new => Some(Self::Leak),
+            ty::Adt(adt, _) if is_owning_type(cx, *adt) => match name.as_str() {
+                "from_raw" | "from_raw_in" | "from_raw_parts" | "from_raw_parts_in" if !is_ref_counted(cx, *adt) => {
+                    Some(Self::FromRaw(cx.tcx.item_name(adt.did()), name))
+                },
+                "into_raw" | "into_raw_with_allocator" | "into_raw_parts" | "into_raw_parts_with_alloc" | "leak" => {
+                    Some(Self::Leak)
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+fn is_owning_type(cx: &LateContext<'_>, adt: AdtDef<'_>) -> bool {
+    adt.is_box()
+        || cx.tcx.get_diagnostic_name(adt.did()).map_or(false, |name| {
+            matches!(name, sym::Vec | sym::String | sym::Rc | sym::Arc | sym::cstring_type)
+        })
+}
+
+fn is_ref_counted(cx: &LateContext<'_>, adt: AdtDef<'_>) -> bool {
+    matches!(cx.tcx.get_diagnostic_name(adt.did()), Some(sym::Rc | sym::Arc))
+}
+
+struct Call<'tcx> {
+    kind: RawCall,
+    block: BasicBlock,
+    target: BasicBlock,
+    /// The first argument of the call.
+    arg: Place<'tcx>,
+    destination: Place<'tcx>,
+    span: Span,
+}
+
+fn check_double_from_raw<'tcx>(cx: &LateContext<'tcx>, mir: &mir::Body<'tcx>, calls: &[Call<'tcx>]) {
+    let from_raw_calls: Vec<_> = calls
+        .iter()
+        .filter(|call| matches!(call.kind, RawCall::FromRaw(..)))
+        .filter(|call| {
+            !matches!(call.arg.ty(mir, cx.tcx).ty.kind(), ty::RawPtr(ty_and_mut) if is_c_void(cx, ty_and_mut.ty))
+        })
+        .map(|call| (call, origin_place(mir, call.arg)))
+        .collect();
+
+    for &(first, ptr) in &from_raw_calls {
+        if let Some(first_result) = first.destination.as_local()
+            && is_leaked(cx, mir, first_result)
+        {
+            continue;
+        }
+
+        let second = find_reachable(mir, first.target, |location| {
+            if let Some(stmt) = mir.basic_blocks[location.block]
+                .statements
+                .get(location.statement_index)
+            {
+                return if let StatementKind::Assign(box (place, _)) = &stmt.kind
+                    && place.local == ptr.local
+                {
+                    Flow::Stop
+                } else {
+                    Flow::Continue
+                };
+            }
+            if let Some((second, _)) = from_raw_calls
+                .iter()
+                .find(|(second, second_ptr)| second.block == location.block && *second_ptr == ptr)
+                && second.block != first.block
+            {
+                Flow::Found(second)
+            } else if let TerminatorKind::Call { destination, .. } = &mir.basic_blocks[location.block].terminator().kind
+                && destination.local == ptr.local
+            {
+                Flow::Stop
+            } else {
+                Flow::Continue
+            }
+        });
+
+        if let Some(second) = second
+            && let (RawCall::FromRaw(first_ty, first_fn), RawCall::FromRaw(second_ty, second_fn)) =
+                (first.kind, second.kind)
+        {
+            span_lint_hir_and_then(
+                cx,
+                DOUBLE_FROM_RAW,
+                lint_root(mir, second.block),
+                second.span,
+                &format!("`{second_ty}::{second_fn}` takes ownership of a pointer which is already owned"),
+                |diag| {
+                    diag.span_note(first.span, format!("ownership was first taken by this `{first_ty}::{first_fn}`"));
+                    diag.help("both values will free the same allocation once they are dropped");
+                },
+            );
+        }
+    }
+}
+
+fn check_drop_after_ptr_read<'tcx>(cx: &LateContext<'tcx>, mir: &mir::Body<'tcx>, calls: &[Call<'tcx>]) {
+    for read in calls.iter().filter(|call| call.kind == RawCall::PtrRead) {
+        let Some(src) = read.arg.as_local().and_then(|ptr| pointee_local(mir, ptr)) else {
+            continue;
+        };
+        let src_ty = mir.local_decls[src].ty;
+        if !src_ty.needs_drop(cx.tcx, cx.param_env)
+            || src_ty.ty_adt_def().map_or(false, AdtDef::is_manually_drop)
+            || read.destination.ty(mir, cx.tcx).ty != src_ty
+            || read
+                .destination
+                .as_local()
+                .map_or(true, |dest| is_leaked(cx, mir, dest))
+        {
+            continue;
+        }
+
+        let drop = find_reachable(mir, read.target, |location| {
+            let bbdata = &mir.basic_blocks[location.block];
+            if let Some(stmt) = bbdata.statements.get(location.statement_index) {
+                return match &stmt.kind {
+                    StatementKind::Assign(box (place, _)) if place.local == src => Flow::Stop,
+                    StatementKind::Assign(box (
+                        _,
+                        Rvalue::Ref(_, BorrowKind::Mut { .. }, place) | Rvalue::AddressOf(Mutability::Mut, place),
+                    )) if place.local == src => Flow::Stop,
+                    _ => Flow::Continue,
+                };
+            }
+            let terminator = bbdata.terminator();
+            match &terminator.kind {
+                TerminatorKind::Drop { place, .. } if place.as_local() == Some(src) => {
+                    Flow::Found(terminator.source_info.span)
+                },
+                TerminatorKind::Call { destination, .. } if destination.local == src => Flow::Stop,
+                TerminatorKind::Call { .. }
+                    if let Some(call) = calls.iter().find(|call| call.block == location.block)
+                        && call.kind == RawCall::Drop
+                        && call.arg.as_local().map(|arg| moved_from(mir, arg)) == Some(src) =>
+                {
+                    Flow::Found(call.span)
+                },
+                _ => Flow::Continue,
+            }
+        });
+
+        if let Some(drop_span) = drop {
+            span_lint_hir_and_then(
+                cx,
+                DROP_AFTER_PTR_READ,
+                lint_root(mir, read.block),
+                read.span,
+                "this duplicates a value which is dropped again later",
+                |diag| {
+                    diag.span_note(drop_span, "the original value is dropped here");
+                    diag.help("use `mem::forget` on the original value after reading it, or wrap it in `ManuallyDrop`");
+                },
+            );
+        }
+    }
+}
+
+enum Flow<T> {
+    Continue,
+    /// Don't look at the rest of the current path.
+    Stop,
+    Found(T),
+}
+
+/// Visits every location reachable from the start of `start`, ignoring cleanup blocks, until `f`
+/// returns `Flow::Found`.
+fn find_reachable<T>(mir: &mir::Body<'_>, start: BasicBlock, mut f: impl FnMut(Location) -> Flow<T>) -> Option<T> {
+    let mut visited = BitSet::new_empty(mir.basic_blocks.len());
+    let mut stack = vec![start];
+    'blocks: while let Some(block) = stack.pop() {
+        let bbdata = &mir.basic_blocks[block];
+        if bbdata.is_cleanup || !visited.insert(block) {
+            continue;
+        }
+        for statement_index in 0..=bbdata.statements.len() {
+            match f(Location { block, statement_index }) {
+                Flow::Continue => {},
+                Flow::Stop => continue 'blocks,
+                Flow::Found(x) => return Some(x),
+            }
+        }
+        stack.extend(bbdata.terminator().successors());
+    }
+    None
+}
+
+/// Returns the `Rvalue` assigned to `local`, if it's assigned exactly once by a statement.
+fn assigned_rvalue<'a, 'tcx>(mir: &'a mir::Body<'tcx>, local: Local) -> Option<&'a Rvalue<'tcx>> {
+    // Arguments already hold a value before any assignment.
+    if mir.local_kind(local) == LocalKind::Arg {
+        return None;
+    }
+    let [location] = *local_assignments(mir, local) else {
+        return None;
+    };
+    match &mir.basic_blocks[location.block]
+        .statements
+        .get(location.statement_index)?
+        .kind
+    {
+        StatementKind::Assign(box (_, rvalue)) => Some(rvalue),
+        _ => None,
+    }
+}
+
+/// Follows copies, moves and casts of `place` back to the place they originate from.
+fn origin_place<'tcx>(mir: &mir::Body<'tcx>, mut place: Place<'tcx>) -> Place<'tcx> {
+    for _ in 0..mir.local_decls.len() {
+        if let Some(local) = place.as_local()
+            && let Some(
+                Rvalue::Use(Operand::Copy(src) | Operand::Move(src))
+                | Rvalue::Cast(_, Operand::Copy(src) | Operand::Move(src), _),
+            ) = assigned_rvalue(mir, local)
+        {
+            place = *src;
+        } else {
+            break;
+        }
+    }
+    place
+}
+
+/// Follows moves of `local` back to the local they originate from.
+fn moved_from(mir: &mir::Body<'_>, mut local: Local) -> Local {
+    for _ in 0..mir.local_decls.len() {
+        if let Some(Rvalue::Use(Operand::Move(src))) = assigned_rvalue(mir, local)
+            && let Some(src) = src.as_local()
+        {
+            local = src;
+        } else {
+            break;
+        }
+    }
+    local
+}
+
+/// Returns the local `ptr` points to, looking through reborrows and pointer casts.
+fn pointee_local(mir: &mir::Body<'_>, mut ptr: Local) -> Option<Local> {
+    for _ in 0..mir.local_decls.len() {
+        let place = origin_place(mir, Place::from(ptr));
+        let (Rvalue::Ref(_, _, pointee) | Rvalue::AddressOf(_, pointee)) = assigned_rvalue(mir, place.as_local()?)?
+        else {
+            return None;
+        };
+        match pointee.as_ref().projection {
+            [] => return Some(pointee.local),
+            [mir::ProjectionElem::Deref] => ptr = pointee.local,
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Checks if the value in `local` is given up without being dropped, e.g. by passing it to
+/// `mem::forget`.
+fn is_leaked<'tcx>(cx: &LateContext<'tcx>, mir: &mir::Body<'tcx>, local: Local) -> bool {
+    mir.basic_blocks.iter().any(|bbdata| {
+        if let TerminatorKind::Call { func, args, .. } = &bbdata.terminator().kind
+            && let ty::FnDef(fn_id, _) = *func.ty(mir, cx.tcx).kind()
+            && let Some(Operand::Move(arg)) = args.first()
+            && let Some(arg) = arg.as_local()
+        {
+            moved_from(mir, arg) == local && RawCall::classify(cx, fn_id) == Some(RawCall::Leak)
+        } else {
+            false
+        }
+    })
+}
+
+fn lint_root(mir: &mir::Body<'_>, block: BasicBlock) -> rustc_hir::HirId {
+    let scope = mir.basic_blocks[block].terminator().source_info.scope;
+    mir.source_scopes[scope]
+        .local_data
+        .as_ref()
+        .assert_crate_local()
+        .lint_root
+}
@@ -2,20 +2,26 @@ use std::fmt::Display;
 
 use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_help};
+use clippy_utils::higher::VecArgs;
 use clippy_utils::source::snippet_opt;
-use clippy_utils::{def_path_def_ids, path_def_id, paths};
+use clippy_utils::visitors::{for_each_expr, Descend, Visitable};
+use clippy_utils::{def_path_def_ids, get_parent_expr, is_trait_method, path_def_id, paths};
+use core::ops::ControlFlow;
 use rustc_ast::ast::{LitKind, StrStyle};
 use rustc_hir::def_id::DefIdMap;
 use rustc_hir::{BorrowKind, Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::source_map::{BytePos, Span};
+use rustc_span::sym;
 
 declare_clippy_lint! {
     /// ### What it does
     /// Checks [regex](https://crates.io/crates/regex) creation
     /// (with `Regex::new`, `RegexBuilder::new`, or `RegexSet::new`) for correct
-    /// regex syntax.
+    /// regex syntax. The constructors of [`regex-lite`](https://crates.io/crates/regex-lite)
+    /// and [`fancy-regex`](https://crates.io/crates/fancy-regex), and the functions listed in
+    /// the `regex-constructors` configuration are checked as well.
     ///
     /// ### Why is this bad?
     /// This will lead to a runtime panic.
@@ -33,7 +39,8 @@ declare_clippy_lint! {
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for trivial [regex](https://crates.io/crates/regex)
-    /// creation (with `Regex::new`, `RegexBuilder::new`, or `RegexSet::new`).
+    /// creation (with `Regex::new`, `RegexBuilder::new`, or `RegexSet::new`,
+    /// or any of the constructors checked by `invalid_regex`).
     ///
     /// ### Why is this bad?
     /// Matching the regex can likely be replaced by `==` or
@@ -55,20 +62,102 @@ declare_clippy_lint! {
     "trivial regular expressions"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for regexes compiled from a constant pattern inside loops, or inside closures
+    /// passed to iterator adapters.
+    ///
+    /// ### Why is this bad?
+    /// Compiling a regex is expensive, and the same regex is compiled again for every
+    /// iteration. It should be compiled once before the loop, or cached in a `static`.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// for line in text.lines() {
+    ///     let re = Regex::new("^[0-9]+$").unwrap();
+    ///     if re.is_match(line) {
+    ///         // ..
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let re = Regex::new("^[0-9]+$").unwrap();
+    /// for line in text.lines() {
+    ///     if re.is_match(line) {
+    ///         // ..
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub REGEX_CREATION_IN_LOOPS,
+    perf,
+    "regular expressions compiled inside loops"
+}
+
 #[derive(Copy, Clone)]
 enum RegexKind {
     Unicode,
     UnicodeSet,
     Bytes,
     BytesSet,
+    /// `fancy-regex`, which extends the syntax of `regex` with backreferences, look-around and
+    /// a few other constructs.
+    Fancy,
 }
 
-#[derive(Default)]
 pub struct Regex {
     definitions: DefIdMap<RegexKind>,
+    regex_constructors: Vec<String>,
+}
+
+impl Regex {
+    pub fn new(regex_constructors: Vec<String>) -> Self {
+        Self {
+            definitions: DefIdMap::default(),
+            regex_constructors,
+        }
+    }
+
+    /// Lints the regexes compiled with a constant pattern in `body`, which is evaluated
+    /// repeatedly by the loop or closure at `repeated_span`. `repeated` describes it in the
+    /// message, `noun` in the help.
+    fn check_creations<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        body: impl Visitable<'tcx>,
+        repeated_span: Span,
+        repeated: &str,
+        noun: &str,
+    ) {
+        for_each_expr(body, |e| {
+            match e.kind {
+                // Nested loops and closures are checked on their own. Other closures are usually
+                // called once, e.g. to initialize a `OnceLock`.
+                ExprKind::Loop(..) | ExprKind::Closure(_) => return ControlFlow::Continue(Descend::No),
+                ExprKind::Call(fun, [arg, ..])
+                    if !e.span.from_expansion()
+                        && let Some(def_id) = path_def_id(cx, fun)
+                        && let Some(&regex_kind) = self.definitions.get(&def_id)
+                        && is_constant_pattern(cx, arg, regex_kind) =>
+                {
+                    span_lint_and_help(
+                        cx,
+                        REGEX_CREATION_IN_LOOPS,
+                        e.span,
+                        &format!("compiling a regex in {repeated}"),
+                        Some(repeated_span),
+                        &format!("move the regex construction outside this {noun}"),
+                    );
+                },
+                _ => {},
+            }
+            ControlFlow::<(), _>::Continue(Descend::Yes)
+        });
+    }
 }
 
-impl_lint_pass!(Regex => [INVALID_REGEX, TRIVIAL_REGEX]);
+impl_lint_pass!(Regex => [INVALID_REGEX, TRIVIAL_REGEX, REGEX_CREATION_IN_LOOPS]);
 
 impl<'tcx> LateLintPass<'tcx> for Regex {
     fn check_crate(&mut self, cx: &LateContext<'tcx>) {
@@ -77,7 +166,7 @@ impl<'tcx> LateLintPass<'tcx> for Regex {
         //
         // `def_path_def_ids` will resolve through re-exports but is relatively heavy, so we only perform
         // the operation once and store the results
-        let mut resolve = |path, kind| {
+        let mut resolve = |path: &[&str], kind| {
             for id in def_path_def_ids(cx, path) {
                 self.definitions.insert(id, kind);
             }
@@ -86,26 +175,81 @@ impl<'tcx> LateLintPass<'tcx> for Regex {
         resolve(&paths::REGEX_NEW, RegexKind::Unicode);
         resolve(&paths::REGEX_BUILDER_NEW, RegexKind::Unicode);
         resolve(&paths::REGEX_SET_NEW, RegexKind::UnicodeSet);
+        resolve(&paths::REGEX_SET_BUILDER_NEW, RegexKind::UnicodeSet);
         resolve(&paths::REGEX_BYTES_NEW, RegexKind::Bytes);
         resolve(&paths::REGEX_BYTES_BUILDER_NEW, RegexKind::Bytes);
         resolve(&paths::REGEX_BYTES_SET_NEW, RegexKind::BytesSet);
+        resolve(&paths::REGEX_BYTES_SET_BUILDER_NEW, RegexKind::BytesSet);
+        resolve(&paths::REGEX_LITE_NEW, RegexKind::Unicode);
+        resolve(&paths::REGEX_LITE_BUILDER_NEW, RegexKind::Unicode);
+        resolve(&paths::FANCY_REGEX_NEW, RegexKind::Fancy);
+        resolve(&paths::FANCY_REGEX_BUILDER_NEW, RegexKind::Fancy);
+
+        for path in &self.regex_constructors {
+            let path: Vec<&str> = path.split("::").collect();
+            resolve(&path, RegexKind::Unicode);
+        }
     }
 
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
-        if let ExprKind::Call(fun, [arg]) = expr.kind
-            && let Some(def_id) = path_def_id(cx, fun)
-            && let Some(regex_kind) = self.definitions.get(&def_id)
-        {
-            match regex_kind {
-                RegexKind::Unicode => check_regex(cx, arg, true),
-                RegexKind::UnicodeSet => check_set(cx, arg, true),
-                RegexKind::Bytes => check_regex(cx, arg, false),
-                RegexKind::BytesSet => check_set(cx, arg, false),
-            }
+        match expr.kind {
+            ExprKind::Call(fun, [arg, ..])
+                if let Some(def_id) = path_def_id(cx, fun)
+                    && let Some(&regex_kind) = self.definitions.get(&def_id) =>
+            {
+                match regex_kind {
+                    RegexKind::Unicode | RegexKind::Bytes | RegexKind::Fancy => check_regex(cx, arg, regex_kind),
+                    RegexKind::UnicodeSet => check_set(cx, arg, RegexKind::Unicode),
+                    RegexKind::BytesSet => check_set(cx, arg, RegexKind::Bytes),
+                }
+            },
+            ExprKind::Loop(block, ..) => self.check_creations(cx, block, expr.span, "a loop", "loop"),
+            ExprKind::Closure(closure)
+                if let Some(parent) = get_parent_expr(cx, expr)
+                    && let ExprKind::MethodCall(_, _, args, _) = parent.kind
+                    && args.iter().any(|arg| arg.hir_id == expr.hir_id)
+                    && is_trait_method(cx, parent, sym::Iterator) =>
+            {
+                let body = cx.tcx.hir().body(closure.body);
+                self.check_creations(
+                    cx,
+                    body.value,
+                    expr.span,
+                    "a closure called for every item",
+                    "closure",
+                );
+            },
+            _ => {},
         }
     }
 }
 
+/// Returns the patterns of a regex set, passed as an array, a reference to an array or a
+/// `vec!`.
+fn set_patterns<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) -> Option<&'tcx [Expr<'tcx>]> {
+    let expr = if let ExprKind::AddrOf(BorrowKind::Ref, _, inner) = expr.kind {
+        inner
+    } else {
+        expr
+    };
+    if let ExprKind::Array(exprs) = expr.kind {
+        Some(exprs)
+    } else if let Some(VecArgs::Vec(exprs)) = VecArgs::hir(cx, expr) {
+        Some(exprs)
+    } else {
+        None
+    }
+}
+
+fn is_constant_pattern<'tcx>(cx: &LateContext<'tcx>, arg: &'tcx Expr<'_>, regex_kind: RegexKind) -> bool {
+    match regex_kind {
+        RegexKind::UnicodeSet | RegexKind::BytesSet => {
+            set_patterns(cx, arg).map_or(false, |exprs| exprs.iter().all(|e| const_str(cx, e).is_some()))
+        },
+        RegexKind::Unicode | RegexKind::Bytes | RegexKind::Fancy => const_str(cx, arg).is_some(),
+    }
+}
+
 fn lint_syntax_error(cx: &LateContext<'_>, error: &regex_syntax::Error, unescaped: &str, base: Span, offset: u8) {
     let parts: Option<(_, _, &dyn Display)> = match &error {
         regex_syntax::Error::Parse(e) => Some((e.span(), e.auxiliary_span(), e.kind())),
@@ -190,19 +334,44 @@ fn is_trivial_regex(s: &regex_syntax::hir::Hir) -> Option<&'static str> {
     }
 }
 
-fn check_set<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, utf8: bool) {
-    if_chain! {
-        if let ExprKind::AddrOf(BorrowKind::Ref, _, expr) = expr.kind;
-        if let ExprKind::Array(exprs) = expr.kind;
-        then {
-            for expr in exprs {
-                check_regex(cx, expr, utf8);
-            }
+/// Checks each pattern of a set on its own, as they may use different flags.
+fn check_set<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, regex_kind: RegexKind) {
+    if let Some(exprs) = set_patterns(cx, expr) {
+        for expr in exprs {
+            check_regex(cx, expr, regex_kind);
         }
     }
 }
 
-fn check_regex<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, utf8: bool) {
+/// Whether `error` is caused by syntax which `regex_syntax` doesn't support, but the regex engine
+/// does.
+fn is_unsupported_syntax(error: &regex_syntax::Error, regex_kind: RegexKind) -> bool {
+    use regex_syntax::ast::ErrorKind::{
+        EscapeUnrecognized, FlagUnrecognized, UnsupportedBackreference, UnsupportedLookAround,
+    };
+
+    // `fancy-regex` adds backreferences (`\1`, `\k<name>`), look-around, atomic groups
+    // (`(?>..)`), conditionals (`(?(1)..)`), `\K` and `\G`
+    if let RegexKind::Fancy = regex_kind
+        && let regex_syntax::Error::Parse(e) = error
+    {
+        let pattern = e.pattern();
+        let start = e.span().start.offset;
+        match e.kind() {
+            UnsupportedBackreference | UnsupportedLookAround => true,
+            EscapeUnrecognized => matches!(pattern[start..].chars().nth(1), Some('K' | 'G' | 'k')),
+            FlagUnrecognized => {
+                pattern[..start].ends_with("(?") && matches!(pattern[start..].chars().next(), Some('>' | '('))
+            },
+            _ => false,
+        }
+    } else {
+        false
+    }
+}
+
+fn check_regex<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, regex_kind: RegexKind) {
+    let utf8 = !matches!(regex_kind, RegexKind::Bytes | RegexKind::BytesSet);
     let mut parser = regex_syntax::ParserBuilder::new().unicode(true).utf8(utf8).build();
 
     if let ExprKind::Lit(lit) = expr.kind {
@@ -215,6 +384,7 @@ fn check_regex<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, utf8: bool) {
                         span_lint_and_help(cx, TRIVIAL_REGEX, expr.span, "trivial regex", None, repl);
                     }
                 },
+                Err(e) if is_unsupported_syntax(&e, regex_kind) => {},
                 Err(e) => lint_syntax_error(cx, &e, r, expr.span, offset),
             }
         }
@@ -225,6 +395,7 @@ fn check_regex<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, utf8: bool) {
                     span_lint_and_help(cx, TRIVIAL_REGEX, expr.span, "trivial regex", None, repl);
                 }
             },
+            Err(e) if is_unsupported_syntax(&e, regex_kind) => {},
             Err(e) => span_lint(cx, INVALID_REGEX, expr.span, &e.to_string()),
         }
     }
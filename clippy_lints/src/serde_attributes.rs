@@ -0,0 +1,515 @@
+use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_then};
+use clippy_utils::ty::implements_trait;
+use clippy_utils::{get_trait_def_id, paths};
+use rustc_ast::{Attribute, NestedMetaItem};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{FieldDef, HirId, Item, ItemKind, Variant, VariantData};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::symbol::kw;
+use rustc_span::{sym, Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for fields or variants of a type deriving `Serialize` or `Deserialize` that end up
+    /// with the same name, through `#[serde(rename)]` and `#[serde(rename_all)]`.
+    ///
+    /// ### Why is this bad?
+    /// Serde doesn't check for duplicate names: one of the fields is never deserialized, and the
+    /// serialized output contains the same key twice.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct User {
+    ///     user_id: u32,
+    ///     #[serde(rename = "userId")]
+    ///     legacy_id: u32,
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub SERDE_RENAME_CONFLICT,
+    suspicious,
+    "fields or variants serialized with the same name"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[serde(deny_unknown_fields)]` on structs containing a `#[serde(flatten)]`
+    /// field, or on the type of a flattened field.
+    ///
+    /// ### Why is this bad?
+    /// Serde doesn't support the combination: the fields of the flattened type are unknown to the
+    /// outer one and the other way around, so deserialization fails at runtime.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use std::collections::HashMap;
+    /// #[derive(Deserialize)]
+    /// #[serde(deny_unknown_fields)]
+    /// struct Config {
+    ///     name: String,
+    ///     #[serde(flatten)]
+    ///     extra: HashMap<String, String>,
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+    suspicious,
+    "`#[serde(deny_unknown_fields)]` combined with `#[serde(flatten)]`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for fields with `#[serde(skip_serializing_if)]` in types implementing both
+    /// `Serialize` and `Deserialize`, which have no default value for deserialization.
+    ///
+    /// ### Why is this bad?
+    /// When the field is skipped, the serialized value can't be deserialized again because the
+    /// field is missing.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Page {
+    ///     #[serde(skip_serializing_if = "Vec::is_empty")]
+    ///     items: Vec<String>,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Page {
+    ///     #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ///     items: Vec<String>,
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub SERDE_SKIP_SERIALIZING_WITHOUT_DEFAULT,
+    suspicious,
+    "`#[serde(skip_serializing_if)]` on a field without a default value"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for variants of `#[serde(untagged)]` enums which are never deserialized, because an
+    /// earlier variant accepts the same input: two unit variants, newtype or tuple variants with
+    /// the same field types, or a struct variant whose fields are all present in a later one.
+    ///
+    /// ### Why is this bad?
+    /// Untagged enums are deserialized by trying each variant in order, so the later variant is
+    /// unreachable.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize)]
+    /// #[serde(untagged)]
+    /// enum Shape {
+    ///     Circle { radius: f64 },
+    ///     Ring { radius: f64, width: f64 },
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize)]
+    /// #[serde(untagged)]
+    /// enum Shape {
+    ///     Ring { radius: f64, width: f64 },
+    ///     Circle { radius: f64 },
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub SERDE_UNTAGGED_OVERLAP,
+    suspicious,
+    "variants of untagged enums shadowed by an earlier variant"
+}
+
+declare_lint_pass!(SerdeAttributes => [
+    SERDE_RENAME_CONFLICT,
+    SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+    SERDE_SKIP_SERIALIZING_WITHOUT_DEFAULT,
+    SERDE_UNTAGGED_OVERLAP,
+]);
+
+impl<'tcx> LateLintPass<'tcx> for SerdeAttributes {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if item.span.from_expansion() {
+            return;
+        }
+        let container = SerdeAttrs::new(cx, item.hir_id());
+        match item.kind {
+            ItemKind::Struct(ref data, _) => {
+                let fields = data.fields();
+                if container.is_empty() && fields.iter().all(|field| SerdeAttrs::new(cx, field.hir_id).is_empty()) {
+                    return;
+                }
+                if let Some(rule) = container.rename_rule("rename_all") {
+                    check_field_names(cx, rule, fields);
+                }
+                check_flatten(cx, &container, fields);
+                if container.word(kw::Default).is_none() && implements_deserialize(cx, item.owner_id.to_def_id()) {
+                    check_skip_serializing_if(cx, fields);
+                }
+            },
+            ItemKind::Enum(ref def, _) => {
+                let variants = def
+                    .variants
+                    .iter()
+                    .map(|variant| (variant, SerdeAttrs::new(cx, variant.hir_id)))
+                    .collect::<Vec<_>>();
+                let has_field_attrs = || {
+                    def.variants
+                        .iter()
+                        .flat_map(|variant| variant.data.fields())
+                        .any(|field| !SerdeAttrs::new(cx, field.hir_id).is_empty())
+                };
+                if container.is_empty() && variants.iter().all(|(_, attrs)| attrs.is_empty()) && !has_field_attrs() {
+                    return;
+                }
+
+                if let Some(rename_all) = container.rename_rule("rename_all") {
+                    let names = variants
+                        .iter()
+                        .filter(|(_, attrs)| attrs.word(sym::skip).is_none())
+                        .map(|(variant, attrs)| {
+                            let name = attrs.name(|| rename_all.apply_to_variant(variant.ident.name))?;
+                            Some((name, variant.ident.name, variant.span))
+                        })
+                        .collect::<Option<Vec<_>>>();
+                    if let Some(names) = names {
+                        check_duplicate_names(cx, &names, "variant");
+                    }
+                }
+
+                if let Some(enum_rule) = container.rename_rule("rename_all_fields") {
+                    for (variant, attrs) in &variants {
+                        // Like in `serde_derive`, the rule of the variant takes precedence
+                        if let Some(rule) = attrs.rename_rule("rename_all") {
+                            let rule = if rule == RenameRule::None { enum_rule } else { rule };
+                            check_field_names(cx, rule, variant.data.fields());
+                        }
+                    }
+                }
+
+                if container.word(sym!(untagged)).is_some() && container.word(sym!(deny_unknown_fields)).is_none() {
+                    check_untagged_overlap(cx, &variants);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// The `#[serde(..)]` attributes of an item, variant or field.
+struct SerdeAttrs {
+    items: Vec<NestedMetaItem>,
+}
+
+impl SerdeAttrs {
+    fn new(cx: &LateContext<'_>, hir_id: HirId) -> Self {
+        Self {
+            items: cx
+                .tcx
+                .hir()
+                .attrs(hir_id)
+                .iter()
+                .filter(|attr| attr.has_name(sym!(serde)))
+                .filter_map(Attribute::meta_item_list)
+                .flatten()
+                .collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn get(&self, name: Symbol) -> Option<&NestedMetaItem> {
+        self.items.iter().find(|item| item.has_name(name))
+    }
+
+    /// Returns the span of `name` or `name = ".."`.
+    fn word(&self, name: Symbol) -> Option<Span> {
+        self.get(name).map(NestedMetaItem::span)
+    }
+
+    /// Returns the name given by `rename = ".."`, or `default`. Returns `None` if the name is
+    /// given separately for serialization and deserialization.
+    fn name(&self, default: impl FnOnce() -> Symbol) -> Option<Symbol> {
+        match self.get(sym!(rename)) {
+            Some(item) => item.value_str(),
+            None => Some(default()),
+        }
+    }
+
+    /// Returns the rule given by `name = ".."`, `None` if it is unknown or given separately for
+    /// serialization and deserialization.
+    fn rename_rule(&self, name: &str) -> Option<RenameRule> {
+        match self.get(Symbol::intern(name)) {
+            Some(item) => RenameRule::from_str(item.value_str()?.as_str()),
+            None => Some(RenameRule::None),
+        }
+    }
+}
+
+/// The case conversions of `#[serde(rename_all = "..")]`, as implemented by `serde_derive`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RenameRule {
+    None,
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Option<Self> {
+        Some(match rule {
+            "lowercase" => Self::LowerCase,
+            "UPPERCASE" => Self::UpperCase,
+            "PascalCase" => Self::PascalCase,
+            "camelCase" => Self::CamelCase,
+            "snake_case" => Self::SnakeCase,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnakeCase,
+            "kebab-case" => Self::KebabCase,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebabCase,
+            _ => return None,
+        })
+    }
+
+    /// Converts the name of a `PascalCase` variant.
+    fn apply_to_variant(self, variant: Symbol) -> Symbol {
+        let variant = variant.as_str();
+        let snake_case = || {
+            let mut snake = String::new();
+            for (i, ch) in variant.char_indices() {
+                if i > 0 && ch.is_uppercase() {
+                    snake.push('_');
+                }
+                snake.push(ch.to_ascii_lowercase());
+            }
+            snake
+        };
+        Symbol::intern(&match self {
+            Self::None | Self::PascalCase => return Symbol::intern(variant),
+            Self::LowerCase => variant.to_ascii_lowercase(),
+            Self::UpperCase => variant.to_ascii_uppercase(),
+            Self::CamelCase => lowercase_first(variant),
+            Self::SnakeCase => snake_case(),
+            Self::ScreamingSnakeCase => snake_case().to_ascii_uppercase(),
+            Self::KebabCase => snake_case().replace('_', "-"),
+            Self::ScreamingKebabCase => snake_case().to_ascii_uppercase().replace('_', "-"),
+        })
+    }
+
+    /// Converts the name of a `snake_case` field.
+    fn apply_to_field(self, field: Symbol) -> Symbol {
+        let field = field.as_str();
+        let pascal_case = || {
+            let mut pascal = String::new();
+            let mut capitalize = true;
+            for ch in field.chars() {
+                if ch == '_' {
+                    capitalize = true;
+                } else if capitalize {
+                    pascal.push(ch.to_ascii_uppercase());
+                    capitalize = false;
+                } else {
+                    pascal.push(ch);
+                }
+            }
+            pascal
+        };
+        Symbol::intern(&match self {
+            Self::None | Self::LowerCase | Self::SnakeCase => return Symbol::intern(field),
+            Self::UpperCase | Self::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            Self::PascalCase => pascal_case(),
+            Self::CamelCase => lowercase_first(&pascal_case()),
+            Self::KebabCase => field.replace('_', "-"),
+            Self::ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+        })
+    }
+}
+
+fn lowercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_lowercase())
+        .into_iter()
+        .chain(chars)
+        .collect()
+}
+
+/// Checks the names of `fields`, renamed with `rule` unless they have an explicit name.
+fn check_field_names(cx: &LateContext<'_>, rule: RenameRule, fields: &[FieldDef<'_>]) {
+    let mut names = Vec::new();
+    for field in fields {
+        let attrs = SerdeAttrs::new(cx, field.hir_id);
+        if attrs.word(sym::skip).is_some() || attrs.word(sym!(flatten)).is_some() || field.is_positional() {
+            continue;
+        }
+        let Some(name) = attrs.name(|| rule.apply_to_field(field.ident.name)) else {
+            return;
+        };
+        names.push((name, field.ident.name, field.span));
+    }
+    check_duplicate_names(cx, &names, "field");
+}
+
+fn check_duplicate_names(cx: &LateContext<'_>, names: &[(Symbol, Symbol, Span)], kind: &str) {
+    let mut seen = FxHashMap::default();
+    for &(name, ident, span) in names {
+        if let Some(&(first_ident, first_span)) = seen.get(&name) {
+            span_lint_and_then(
+                cx,
+                SERDE_RENAME_CONFLICT,
+                span,
+                &format!("{kind} `{ident}` is serialized as `{name}`, like {kind} `{first_ident}`"),
+                |diag| {
+                    diag.span_note(first_span, format!("{kind} `{first_ident}` is declared here"));
+                },
+            );
+        } else {
+            seen.insert(name, (ident, span));
+        }
+    }
+}
+
+fn check_flatten(cx: &LateContext<'_>, container: &SerdeAttrs, fields: &[FieldDef<'_>]) {
+    for field in fields {
+        let Some(flatten_span) = SerdeAttrs::new(cx, field.hir_id).word(sym!(flatten)) else {
+            continue;
+        };
+        if let Some(deny_span) = container.word(sym!(deny_unknown_fields)) {
+            span_lint_and_then(
+                cx,
+                SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+                flatten_span,
+                "`#[serde(flatten)]` is not supported with `#[serde(deny_unknown_fields)]`",
+                |diag| {
+                    diag.span_note(deny_span, "unknown fields are denied here");
+                },
+            );
+        } else if let ty::Adt(adt, _) = cx.tcx.type_of(field.def_id).instantiate_identity().kind()
+            && let Some(local_def_id) = adt.did().as_local()
+            && let Some(deny_span) =
+                SerdeAttrs::new(cx, cx.tcx.hir().local_def_id_to_hir_id(local_def_id)).word(sym!(deny_unknown_fields))
+        {
+            span_lint_and_then(
+                cx,
+                SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+                flatten_span,
+                "`#[serde(flatten)]` is not supported on a type with `#[serde(deny_unknown_fields)]`",
+                |diag| {
+                    diag.span_note(deny_span, "unknown fields are denied here");
+                },
+            );
+        }
+    }
+}
+
+fn implements_deserialize(cx: &LateContext<'_>, did: DefId) -> bool {
+    get_trait_def_id(cx, &paths::SERDE_DESERIALIZE).map_or(false, |deserialize| {
+        let ty = cx.tcx.type_of(did).instantiate_identity();
+        implements_trait(cx, ty, deserialize, &[cx.tcx.lifetimes.re_erased.into()])
+    })
+}
+
+fn check_skip_serializing_if(cx: &LateContext<'_>, fields: &[FieldDef<'_>]) {
+    for field in fields {
+        let attrs = SerdeAttrs::new(cx, field.hir_id);
+        let ty = cx.tcx.type_of(field.def_id).instantiate_identity();
+        // Missing `Option` fields are deserialized as `None`
+        if let Some(span) = attrs.word(sym!(skip_serializing_if))
+            && attrs.word(kw::Default).is_none()
+            && attrs.word(sym!(skip_deserializing)).is_none()
+            && attrs.word(sym!(deserialize_with)).is_none()
+            && attrs.word(sym!(with)).is_none()
+            && !matches!(ty.kind(), ty::Adt(adt, _) if cx.tcx.is_diagnostic_item(sym::Option, adt.did()))
+        {
+            span_lint_and_help(
+                cx,
+                SERDE_SKIP_SERIALIZING_WITHOUT_DEFAULT,
+                span,
+                "this field is skipped when serializing, but required when deserializing",
+                None,
+                "add `default` to the `#[serde(..)]` attribute",
+            );
+        }
+    }
+}
+
+/// The input accepted by a variant of an untagged enum.
+enum Shape<'tcx> {
+    Unit,
+    Tuple(Vec<Ty<'tcx>>),
+    /// The fields, with their types.
+    Struct(FxHashMap<Symbol, Ty<'tcx>>),
+}
+
+impl<'tcx> Shape<'tcx> {
+    /// Whether all the input accepted by `other` is accepted by `self` as well.
+    fn accepts_all_of(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Unit, Self::Unit) => true,
+            (Self::Tuple(tys), Self::Tuple(other_tys)) => tys == other_tys,
+            (Self::Struct(fields), Self::Struct(other_fields)) => {
+                fields.iter().all(|(name, ty)| other_fields.get(name) == Some(ty))
+            },
+            _ => false,
+        }
+    }
+}
+
+fn check_untagged_overlap(cx: &LateContext<'_>, variants: &[(&Variant<'_>, SerdeAttrs)]) {
+    let mut shapes: Vec<(Symbol, Shape<'_>)> = Vec::new();
+    for (variant, attrs) in variants {
+        if attrs.word(sym::skip).is_some() || attrs.word(sym!(skip_deserializing)).is_some() {
+            continue;
+        }
+        let fields = variant.data.fields();
+        let field_attrs = fields
+            .iter()
+            .map(|field| SerdeAttrs::new(cx, field.hir_id))
+            .collect::<Vec<_>>();
+        // Defaults and custom deserialization make the accepted input hard to tell
+        if field_attrs.iter().any(|attrs| !attrs.is_empty()) || attrs.word(sym!(deserialize_with)).is_some() {
+            continue;
+        }
+        let field_ty = |field: &FieldDef<'_>| cx.tcx.type_of(field.def_id).instantiate_identity();
+        let shape = match variant.data {
+            VariantData::Unit(..) => Shape::Unit,
+            VariantData::Tuple(..) => Shape::Tuple(fields.iter().map(field_ty).collect()),
+            VariantData::Struct(..) => {
+                Shape::Struct(fields.iter().map(|field| (field.ident.name, field_ty(field))).collect())
+            },
+        };
+
+        if let Some((shadowing, _)) = shapes.iter().find(|(_, earlier)| earlier.accepts_all_of(&shape)) {
+            span_lint(
+                cx,
+                SERDE_UNTAGGED_OVERLAP,
+                variant.span,
+                &format!(
+                    "variant `{}` of this untagged enum is never deserialized, as `{shadowing}` accepts the same input",
+                    variant.ident
+                ),
+            );
+        }
+        shapes.push((variant.ident.name, shape));
+    }
+}
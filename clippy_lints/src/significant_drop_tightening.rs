@@ -1,8 +1,10 @@
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::source::{indent_of, snippet};
-use clippy_utils::{expr_or_init, get_attr, path_to_local, peel_hir_expr_unary};
+use clippy_utils::source::{indent_of, reindent_multiline, snippet_with_applicability};
+use clippy_utils::sugg::Sugg;
+use clippy_utils::visitors::{for_each_expr_with_closures, is_local_used};
+use clippy_utils::{get_attr, get_parent_expr, path_to_local, path_to_local_id, peel_hir_expr_unary};
 use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
-use rustc_errors::Applicability;
+use rustc_errors::{Applicability, Diagnostic};
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::intravisit::{walk_expr, Visitor};
 use rustc_hir::{self as hir};
@@ -12,6 +14,7 @@ use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::symbol::Ident;
 use rustc_span::{sym, Span, DUMMY_SP};
 use std::borrow::Cow;
+use std::ops::ControlFlow;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -75,10 +78,14 @@ impl<'tcx> LateLintPass<'tcx> for SignificantDropTightening<'tcx> {
         let initial_dummy_stmt = dummy_stmt_expr(body.value);
         let mut ap = AuxParams::new(&mut self.apas, &initial_dummy_stmt);
         StmtsChecker::new(&mut ap, cx, &mut self.seen_types, &mut self.type_cache).visit_body(body);
-        for apa in ap.apas.values() {
+        for (&hir_id, apa) in ap.apas.iter() {
             if apa.counter <= 1 || !apa.has_expensive_expr_after_last_attr {
                 continue;
             }
+            let block = match cx.tcx.hir().find(apa.first_block_hir_id) {
+                Some(hir::Node::Block(block)) => Some(block),
+                _ => None,
+            };
             span_lint_and_then(
                 cx,
                 SIGNIFICANT_DROP_TIGHTENING,
@@ -87,43 +94,14 @@ impl<'tcx> LateLintPass<'tcx> for SignificantDropTightening<'tcx> {
                 |diag| {
                     match apa.counter {
                         0 | 1 => {},
-                        2 => {
-                            let indent = " ".repeat(indent_of(cx, apa.last_stmt_span).unwrap_or(0));
-                            let init_method = snippet(cx, apa.first_method_span, "..");
-                            let usage_method = snippet(cx, apa.last_method_span, "..");
-                            let stmt = if apa.last_bind_ident == Ident::empty() {
-                                format!("\n{indent}{init_method}.{usage_method};")
-                            } else {
-                                format!(
-                                    "\n{indent}let {} = {init_method}.{usage_method};",
-                                    snippet(cx, apa.last_bind_ident.span, ".."),
-                                )
-                            };
-                            diag.span_suggestion_verbose(
-                                apa.first_stmt_span,
-                                "merge the temporary construction with its single usage",
-                                stmt,
-                                Applicability::MaybeIncorrect,
-                            );
-                            diag.span_suggestion(
-                                apa.last_stmt_span,
-                                "remove separated single usage",
-                                "",
-                                Applicability::MaybeIncorrect,
-                            );
-                        },
-                        _ => {
-                            diag.span_suggestion(
-                                apa.last_stmt_span.shrink_to_hi(),
-                                "drop the temporary after the end of its last usage",
-                                format!(
-                                    "\n{}drop({});",
-                                    " ".repeat(indent_of(cx, apa.last_stmt_span).unwrap_or(0)),
-                                    apa.first_bind_ident
-                                ),
-                                Applicability::MaybeIncorrect,
-                            );
+                        2 => suggest_merge(cx, diag, hir_id, apa, block),
+                        _ if let Some(block) = block
+                            && let Some((first_idx, last_idx)) = stmts_range(block, apa)
+                            && !has_bindings_used_after(cx, block, first_idx, last_idx) =>
+                        {
+                            suggest_block(cx, diag, hir_id, apa, block);
                         },
+                        _ => suggest_drop(cx, diag, hir_id, apa, block),
                     }
                     diag.note("this might lead to unnecessary resource contention");
                     diag.span_label(
@@ -139,6 +117,170 @@ impl<'tcx> LateLintPass<'tcx> for SignificantDropTightening<'tcx> {
     }
 }
 
+/// Suggests merging the construction of the temporary into its single use, e.g. turning
+/// `let lock = mutex.lock().unwrap(); let v = *lock;` into `let v = *mutex.lock().unwrap();`.
+fn suggest_merge<'tcx>(
+    cx: &LateContext<'tcx>,
+    diag: &mut Diagnostic,
+    hir_id: hir::HirId,
+    apa: &AuxParamsAttr,
+    block: Option<&'tcx hir::Block<'tcx>>,
+) {
+    let Some(init) = block
+        .and_then(|block| block.stmts.iter().find(|stmt| stmt.span == apa.first_stmt_span))
+        .and_then(|stmt| match stmt.kind {
+            hir::StmtKind::Local(local) => local.init,
+            _ => None,
+        })
+    else {
+        return;
+    };
+    let source_map = cx.sess().source_map();
+    let Ok(first_stmt_ws) = source_map.span_extend_while(apa.first_stmt_span.shrink_to_hi(), char::is_whitespace)
+    else {
+        return;
+    };
+
+    let mut applicability = Applicability::MachineApplicable;
+    let init_sugg = Sugg::hir_with_applicability(cx, init, "..", &mut applicability).maybe_par();
+    let last_stmt = snippet_with_applicability(cx, apa.last_stmt_span, "..", &mut applicability);
+    let (Some(use_start), Some(use_end)) = (
+        (apa.last_use_span.lo() - apa.last_stmt_span.lo()).0.try_into().ok(),
+        (apa.last_use_span.hi() - apa.last_stmt_span.lo()).0.try_into().ok(),
+    ) else {
+        return;
+    };
+    let Some(merged) = last_stmt
+        .get(..use_start)
+        .zip(last_stmt.get(use_end..))
+        .map(|(before, after)| format!("{before}{init_sugg}{after}"))
+    else {
+        return;
+    };
+
+    // Moving the construction of the temporary after other statements changes when it's created.
+    let is_adjacent = first_stmt_ws.hi() == apa.last_stmt_span.lo();
+    if !is_adjacent || !is_tightening_safe(cx, hir_id, apa, block) || apa.has_borrowing_use {
+        applicability = Applicability::MaybeIncorrect;
+    }
+
+    diag.multipart_suggestion(
+        "merge the temporary construction with its single usage",
+        vec![
+            (apa.first_stmt_span.with_hi(first_stmt_ws.hi()), String::new()),
+            (apa.last_stmt_span, merged),
+        ],
+        applicability,
+    );
+}
+
+/// Suggests moving the temporary and all of its uses into their own block.
+fn suggest_block<'tcx>(
+    cx: &LateContext<'tcx>,
+    diag: &mut Diagnostic,
+    hir_id: hir::HirId,
+    apa: &AuxParamsAttr,
+    block: &'tcx hir::Block<'tcx>,
+) {
+    let mut applicability = if is_tightening_safe(cx, hir_id, apa, Some(block)) && !apa.has_borrowing_use {
+        Applicability::MachineApplicable
+    } else {
+        Applicability::MaybeIncorrect
+    };
+    let indent = indent_of(cx, apa.first_stmt_span).unwrap_or(0);
+    let stmts = snippet_with_applicability(cx, apa.first_stmt_span.to(apa.last_stmt_span), "..", &mut applicability);
+    diag.span_suggestion(
+        apa.first_stmt_span.to(apa.last_stmt_span),
+        "limit the scope of the temporary with a block",
+        format!(
+            "{{\n{}{}\n{}}}",
+            " ".repeat(indent + 4),
+            reindent_multiline(stmts, true, Some(indent + 4)),
+            " ".repeat(indent),
+        ),
+        applicability,
+    );
+}
+
+/// Suggests dropping the temporary right after its last use.
+fn suggest_drop<'tcx>(
+    cx: &LateContext<'tcx>,
+    diag: &mut Diagnostic,
+    hir_id: hir::HirId,
+    apa: &AuxParamsAttr,
+    block: Option<&'tcx hir::Block<'tcx>>,
+) {
+    let applicability = if is_tightening_safe(cx, hir_id, apa, block) && !apa.has_borrowing_use && !apa.has_moving_use {
+        Applicability::MachineApplicable
+    } else {
+        Applicability::MaybeIncorrect
+    };
+    diag.span_suggestion(
+        apa.last_stmt_span.shrink_to_hi(),
+        "drop the temporary after the end of its last usage",
+        format!(
+            "\n{}drop({});",
+            " ".repeat(indent_of(cx, apa.last_stmt_span).unwrap_or(0)),
+            apa.first_bind_ident
+        ),
+        applicability,
+    );
+}
+
+/// Checks the requirements shared by all machine applicable suggestions: the last use is a
+/// statement of the block declaring the temporary, and every use of the temporary, including the
+/// ones in closures, was seen by the lint.
+fn is_tightening_safe<'tcx>(
+    cx: &LateContext<'tcx>,
+    hir_id: hir::HirId,
+    apa: &AuxParamsAttr,
+    block: Option<&'tcx hir::Block<'tcx>>,
+) -> bool {
+    let Some(block) = block else {
+        return false;
+    };
+    if stmts_range(block, apa).is_none() || apa.first_stmt_span.from_expansion() || apa.last_stmt_span.from_expansion()
+    {
+        return false;
+    }
+    let mut uses = 0;
+    for_each_expr_with_closures(cx, block, |expr| {
+        if path_to_local_id(expr, hir_id) {
+            uses += 1;
+        }
+        ControlFlow::<()>::Continue(())
+    });
+    uses == apa.counter - 1
+}
+
+/// Returns the indices of the first and last statements using the temporary if both are
+/// statements of `block`.
+fn stmts_range(block: &hir::Block<'_>, apa: &AuxParamsAttr) -> Option<(usize, usize)> {
+    let first_idx = block.stmts.iter().position(|stmt| stmt.span == apa.first_stmt_span)?;
+    let last_idx = block.stmts.iter().position(|stmt| stmt.span == apa.last_stmt_span)?;
+    (first_idx < last_idx).then_some((first_idx, last_idx))
+}
+
+/// Checks if any of the bindings declared by the statements in `first_idx..=last_idx` are used
+/// after them, in which case they can't be moved into a block.
+fn has_bindings_used_after<'tcx>(
+    cx: &LateContext<'tcx>,
+    block: &'tcx hir::Block<'tcx>,
+    first_idx: usize,
+    last_idx: usize,
+) -> bool {
+    let mut is_used = false;
+    for stmt in &block.stmts[first_idx..=last_idx] {
+        if let hir::StmtKind::Local(local) = stmt.kind {
+            local.pat.each_binding_or_first(&mut |_, binding_id, _, _| {
+                is_used |= is_local_used(cx, &block.stmts[last_idx + 1..], binding_id)
+                    || block.expr.map_or(false, |expr| is_local_used(cx, expr, binding_id));
+            });
+        }
+    }
+    is_used
+}
+
 /// Checks the existence of the `#[has_significant_drop]` attribute.
 struct AttrChecker<'cx, 'others, 'tcx> {
     cx: &'cx LateContext<'tcx>,
@@ -302,15 +444,6 @@ impl<'ap, 'lc, 'others, 'stmt, 'tcx> Visitor<'tcx> for StmtsChecker<'ap, 'lc, 'o
                     first_bind_ident: ident,
                     first_block_hir_id: self.ap.curr_block_hir_id,
                     first_block_span: self.ap.curr_block_span,
-                    first_method_span: {
-                        let expr_or_init = expr_or_init(self.cx, expr);
-                        if let hir::ExprKind::MethodCall(_, local_expr, _, span) = expr_or_init.kind {
-                            local_expr.span.to(span)
-                        }
-                        else {
-                            expr_or_init.span
-                        }
-                    },
                     first_stmt_span: self.ap.curr_stmt.span,
                     ..Default::default()
                 };
@@ -319,30 +452,17 @@ impl<'ap, 'lc, 'others, 'stmt, 'tcx> Visitor<'tcx> for StmtsChecker<'ap, 'lc, 'o
             } else {
                 let Some(hir_id) = path_to_local(expr) else { return; };
                 let Some(apa) = self.ap.apas.get_mut(&hir_id) else { return; };
-                match self.ap.curr_stmt.kind {
-                    hir::StmtKind::Local(local) => {
-                        if let hir::PatKind::Binding(_, _, ident, _) = local.pat.kind {
-                            apa.last_bind_ident = ident;
-                        }
-                        if let Some(local_init) = local.init
-                            && let hir::ExprKind::MethodCall(_, _, _, span) = local_init.kind
-                        {
-                            apa.last_method_span = span;
-                        }
-                    },
-                    hir::StmtKind::Semi(semi_expr) => {
-                        if has_drop(semi_expr, &apa.first_bind_ident, self.cx) {
-                            apa.has_expensive_expr_after_last_attr = false;
-                            apa.last_stmt_span = DUMMY_SP;
-                            return;
-                        }
-                        if let hir::ExprKind::MethodCall(_, _, _, span) = semi_expr.kind {
-                            apa.last_method_span = span;
-                        }
-                    },
-                    _ => {},
+                if let hir::StmtKind::Semi(semi_expr) = self.ap.curr_stmt.kind
+                    && has_drop(semi_expr, &apa.first_bind_ident, self.cx)
+                {
+                    apa.has_expensive_expr_after_last_attr = false;
+                    apa.last_stmt_span = DUMMY_SP;
+                    return;
                 }
                 apa.last_stmt_span = self.ap.curr_stmt.span;
+                apa.last_use_span = expr.span;
+                apa.has_borrowing_use |= !is_borrow_free_use(self.cx, expr);
+                apa.has_moving_use |= !is_by_ref_use(self.cx, expr);
                 modify_apa_params(apa);
             }
         }
@@ -389,18 +509,18 @@ struct AuxParamsAttr {
     /// The binding or variable that references the initial construction of the type marked with
     /// `#[has_significant_drop]`.
     first_bind_ident: Ident,
-    /// Similar to `init_bind_ident` but encompasses the right-hand method call.
-    first_method_span: Span,
     /// Similar to `init_bind_ident` but encompasses the whole contained statement.
     first_stmt_span: Span,
 
-    /// The last visited binding or variable span within a block that had any referenced inner type
-    /// marked with `#[has_significant_drop]`.
-    last_bind_ident: Ident,
-    /// Similar to `last_bind_span` but encompasses the right-hand method call.
-    last_method_span: Span,
-    /// Similar to `last_bind_span` but encompasses the whole contained statement.
+    /// The whole statement containing the last reference to anything marked with
+    /// `#[has_significant_drop]`.
     last_stmt_span: Span,
+    /// The span of the last reference to the binding.
+    last_use_span: Span,
+    /// If any reference to the binding results in a value which might borrow from it.
+    has_borrowing_use: bool,
+    /// If any reference to the binding might move it.
+    has_moving_use: bool,
 }
 
 impl Default for AuxParamsAttr {
@@ -411,11 +531,11 @@ impl Default for AuxParamsAttr {
             first_block_hir_id: hir::HirId::INVALID,
             first_bind_ident: Ident::empty(),
             first_block_span: DUMMY_SP,
-            first_method_span: DUMMY_SP,
             first_stmt_span: DUMMY_SP,
-            last_bind_ident: Ident::empty(),
-            last_method_span: DUMMY_SP,
             last_stmt_span: DUMMY_SP,
+            last_use_span: DUMMY_SP,
+            has_borrowing_use: false,
+            has_moving_use: false,
         }
     }
 }
@@ -456,6 +576,33 @@ fn has_drop(expr: &hir::Expr<'_>, first_bind_ident: &Ident, lcx: &LateContext<'_
     false
 }
 
+/// Checks if none of the expressions containing `expr` within its statement can borrow from it.
+fn is_borrow_free_use(cx: &LateContext<'_>, expr: &hir::Expr<'_>) -> bool {
+    for (_, node) in cx.tcx.hir().parent_iter(expr.hir_id) {
+        let hir::Node::Expr(parent) = node else {
+            break;
+        };
+        let has_regions = cx
+            .typeck_results()
+            .expr_ty(parent)
+            .walk()
+            .any(|arg| matches!(arg.unpack(), GenericArgKind::Lifetime(_)));
+        if has_regions {
+            return false;
+        }
+    }
+    true
+}
+
+/// Checks if `expr` is only used by reference, i.e. it's not moved.
+fn is_by_ref_use(cx: &LateContext<'_>, expr: &hir::Expr<'_>) -> bool {
+    !cx.typeck_results().expr_adjustments(expr).is_empty()
+        || matches!(
+            get_parent_expr(cx, expr).map(|parent| parent.kind),
+            Some(hir::ExprKind::AddrOf(..) | hir::ExprKind::Unary(hir::UnOp::Deref, _))
+        )
+}
+
 fn is_inexpensive_expr(expr: &hir::Expr<'_>) -> bool {
     let actual = peel_hir_expr_unary(expr).0;
     let is_path = matches!(actual.kind, hir::ExprKind::Path(_));
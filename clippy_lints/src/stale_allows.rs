@@ -0,0 +1,270 @@
+use clippy_utils::diagnostics::{record_suppression, span_lint_hir_and_then, used_suppressions};
+use clippy_utils::source::snippet_opt;
+use rustc_ast::{Attribute, NestedMetaItem};
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::{Applicability, MultiSpan};
+use rustc_hir::{HirId, MaybeOwner, OwnerId};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::def_id::LOCAL_CRATE;
+use rustc_span::{sym, Span, Symbol};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[allow]` and `#[expect]` attributes of Clippy lints which didn't suppress any
+    /// lint.
+    ///
+    /// When the `CLIPPY_STALE_ALLOWS` environment variable is set to a directory, the attributes
+    /// which suppressed a lint in each run are recorded in that directory, and an attribute is
+    /// only linted if it didn't suppress a lint in any of the recorded runs. This makes it
+    /// possible to run Clippy with every set of features, targets and `cfg`s before removing the
+    /// attributes with `cargo clippy --fix`. Without `CLIPPY_STALE_ALLOWS`, the attributes have
+    /// to be removed manually, as they may be needed in another configuration.
+    ///
+    /// ### Why is this bad?
+    /// The attribute isn't needed anymore, and hides the lint if the code it was added for
+    /// reappears.
+    ///
+    /// ### Known problems
+    /// - Attributes only needed with some features, targets or `cfg`s are linted unless runs
+    ///   with these configurations are recorded.
+    /// - Some lints skip their analysis when they are allowed. The attributes allowing them are
+    ///   treated as used wherever the analysis is skipped, even if the lint wouldn't be emitted.
+    /// - Attributes of `rustc` lints, and attributes generated by macros, are not checked.
+    /// - Attributes of lints from plugins are linted even when they suppressed a lint.
+    ///
+    /// ### Example
+    /// ```rust
+    /// #[allow(clippy::needless_return)]
+    /// fn f() -> u32 {
+    ///     1
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// fn f() -> u32 {
+    ///     1
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub STALE_ALLOWS,
+    restriction,
+    "`#[allow]` or `#[expect]` of a Clippy lint which didn't suppress any lint"
+}
+
+pub struct StaleAllows {
+    /// The directory the suppressions used by each run are recorded in
+    record_dir: Option<PathBuf>,
+}
+
+impl Default for StaleAllows {
+    fn default() -> Self {
+        Self {
+            record_dir: env::var_os("CLIPPY_STALE_ALLOWS").map(PathBuf::from),
+        }
+    }
+}
+
+/// An `#[allow]` or `#[expect]` attribute naming Clippy lints.
+struct LintAttr {
+    hir_id: HirId,
+    span: Span,
+    level: Symbol,
+    /// The spans of the items of the attribute, including the reason
+    items: Vec<Span>,
+    /// The indices of the items which are lints, with whether they are Clippy lints
+    lints: Vec<(usize, bool)>,
+}
+
+impl LintAttr {
+    fn new(cx: &LateContext<'_>, hir_id: HirId, attr: &Attribute) -> Option<Self> {
+        let level = attr.ident()?.name;
+        if !matches!(level, sym::allow | sym::expect) || attr.span.from_expansion() {
+            return None;
+        }
+        let items = attr.meta_item_list()?;
+        let mut lints = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            let Some(item) = item.meta_item().filter(|item| item.is_word()) else {
+                continue;
+            };
+            // The attribute was written by a proc macro
+            if snippet_opt(cx, item.span).map_or(true, |snippet| snippet != pprust::path_to_string(&item.path)) {
+                return None;
+            }
+            lints.push((i, item.path.segments[0].ident.name == sym::clippy));
+        }
+        lints.iter().any(|&(_, is_clippy)| is_clippy).then(|| Self {
+            hir_id,
+            span: attr.span,
+            level,
+            items: items.iter().map(NestedMetaItem::span).collect(),
+            lints,
+        })
+    }
+}
+
+impl_lint_pass!(StaleAllows => [STALE_ALLOWS]);
+
+impl<'tcx> LateLintPass<'tcx> for StaleAllows {
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        // `check_attribute` is called after the crate is walked, without the `HirId` of the
+        // attributes, so they are collected from the attribute maps of the HIR owners instead
+        let mut attrs = Vec::new();
+        for (def_id, owner) in cx.tcx.hir().krate().owners.iter_enumerated() {
+            let MaybeOwner::Owner(info) = owner else {
+                continue;
+            };
+            for &(local_id, owner_attrs) in info.attrs.map.iter() {
+                let hir_id = HirId {
+                    owner: OwnerId { def_id },
+                    local_id,
+                };
+                attrs.extend(owner_attrs.iter().filter_map(|attr| LintAttr::new(cx, hir_id, attr)));
+            }
+        }
+
+        // The lint is only emitted after the used suppressions are known, so the attributes
+        // allowing it are recorded beforehand, for the attributes it would be emitted for
+        let used = used_suppressions();
+        for attr in &attrs {
+            if attr
+                .lints
+                .iter()
+                .any(|&(i, is_clippy)| is_clippy && !used.contains(&attr.items[i]))
+            {
+                record_suppression(cx.tcx.lint_level_at_node(STALE_ALLOWS, attr.hir_id));
+            }
+        }
+        let used = used_suppressions();
+        let mut recorded = FxHashSet::default();
+        // Without the records of the other configurations, the attribute may still be needed in one
+        // of them
+        let mut applicability = Applicability::MaybeIncorrect;
+        if let Some(dir) = &self.record_dir {
+            let used_keys: Vec<String> = attrs
+                .iter()
+                .flat_map(|attr| attr.lints.iter().map(|&(i, _)| attr.items[i]))
+                .filter(|span| used.contains(span))
+                .map(|span| key(cx, span))
+                .collect();
+            match record(cx, dir, &used_keys) {
+                Ok(keys) => {
+                    recorded = keys;
+                    applicability = Applicability::MachineApplicable;
+                },
+                Err(e) => cx.sess().warn(format!(
+                    "error recording the used `allow` attributes to `{}`: {e}",
+                    dir.display()
+                )),
+            }
+        }
+
+        for attr in &attrs {
+            let is_stale = |i: usize| {
+                let span = attr.items[i];
+                !used.contains(&span) && !recorded.contains(&key(cx, span))
+            };
+            let stale: Vec<usize> = attr
+                .lints
+                .iter()
+                .filter(|&&(i, is_clippy)| is_clippy && is_stale(i))
+                .map(|&(i, _)| i)
+                .collect();
+            if !stale.is_empty() {
+                emit(cx, attr, &stale, applicability);
+            }
+        }
+    }
+}
+
+/// Identifies the position of a lint in an attribute across runs.
+fn key(cx: &LateContext<'_>, span: Span) -> String {
+    let lo = cx.sess().source_map().lookup_char_pos(span.lo());
+    format!("{}:{}:{}", lo.file.name.prefer_remapped(), lo.line, lo.col_display + 1)
+}
+
+/// Writes the suppressions used by this run to `dir`, replacing the previous record of the same
+/// run, and returns the suppressions used by all the recorded runs.
+fn record(cx: &LateContext<'_>, dir: &Path, used_keys: &[String]) -> io::Result<FxHashSet<String>> {
+    // The arguments identify the configuration of the run
+    let mut hasher = DefaultHasher::new();
+    env::args().collect::<Vec<_>>().hash(&mut hasher);
+    let file_name = format!(
+        "{}{}-{:016x}.json",
+        cx.tcx.crate_name(LOCAL_CRATE),
+        cx.sess().opts.cg.extra_filename,
+        hasher.finish()
+    );
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(file_name), serde_json::to_string_pretty(used_keys)?)?;
+
+    let mut recorded = FxHashSet::default();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let keys: Vec<String> = serde_json::from_slice(&fs::read(path)?)?;
+            recorded.extend(keys);
+        }
+    }
+    Ok(recorded)
+}
+
+fn emit(cx: &LateContext<'_>, attr: &LintAttr, stale: &[usize], applicability: Applicability) {
+    let names: Vec<String> = stale
+        .iter()
+        .map(|&i| snippet_opt(cx, attr.items[i]).map_or_else(String::new, |name| format!("`{name}`")))
+        .collect();
+    let msg = format!("this `{}` of {} didn't suppress any lint", attr.level, names.join(", "));
+    let spans = MultiSpan::from_spans(stale.iter().map(|&i| attr.items[i]).collect());
+    span_lint_hir_and_then(cx, STALE_ALLOWS, attr.hir_id, spans, &msg, |diag| {
+        if stale.len() < attr.lints.len() {
+            diag.multipart_suggestion(
+                format!("remove {}", if stale.len() == 1 { "it" } else { "them" }),
+                removal_spans(&attr.items, stale)
+                    .into_iter()
+                    .map(|span| (span, String::new()))
+                    .collect(),
+                applicability,
+            );
+        } else if snippet_opt(cx, attr.span).is_some_and(|snippet| snippet.starts_with('#')) {
+            // Remove the line break and the indentation of the next line as well
+            let span = cx
+                .sess()
+                .source_map()
+                .span_extend_while(attr.span, char::is_whitespace)
+                .unwrap_or(attr.span);
+            diag.span_suggestion(span, "remove the attribute", "", applicability);
+        } else {
+            // The attribute comes from `cfg_attr`
+            diag.help("remove the attribute");
+        }
+    });
+}
+
+/// Returns the spans to remove to remove the stale items of an attribute, along with their
+/// separators. Each run of consecutive stale items is removed up to the next item, or from the
+/// previous item if it's at the end.
+fn removal_spans(items: &[Span], stale: &[usize]) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < stale.len() {
+        let start = stale[i];
+        let mut end = start;
+        while i + 1 < stale.len() && stale[i + 1] == end + 1 {
+            i += 1;
+            end += 1;
+        }
+        spans.push(match items.get(end + 1) {
+            Some(next) => items[start].with_hi(next.lo()),
+            None => items[start - 1].shrink_to_hi().with_hi(items[end].hi()),
+        });
+        i += 1;
+    }
+    spans
+}
@@ -2,8 +2,8 @@ use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_sug
 use clippy_utils::source::{snippet, snippet_with_applicability};
 use clippy_utils::ty::is_type_lang_item;
 use clippy_utils::{
-    get_expr_use_or_unification_node, get_parent_expr, is_lint_allowed, is_path_diagnostic_item, method_calls,
-    peel_blocks, SpanlessEq,
+    get_expr_use_or_unification_node, get_parent_expr, is_path_diagnostic_item, method_calls, peel_blocks,
+    should_skip_lint, SpanlessEq,
 };
 use if_chain::if_chain;
 use rustc_errors::Applicability;
@@ -160,7 +160,7 @@ impl<'tcx> LateLintPass<'tcx> for StringAdd {
                 _,
             ) => {
                 if is_string(cx, left) {
-                    if !is_lint_allowed(cx, STRING_ADD_ASSIGN, e.hir_id) {
+                    if !should_skip_lint(cx, STRING_ADD_ASSIGN, e.hir_id) {
                         let parent = get_parent_expr(cx, e);
                         if let Some(p) = parent {
                             if let ExprKind::Assign(target, _, _) = p.kind {
@@ -3,7 +3,7 @@ use std::ops::ControlFlow;
 use clippy_utils::diagnostics::span_lint_and_help;
 use clippy_utils::source::walk_span_to_context;
 use clippy_utils::visitors::{for_each_expr_with_closures, Descend};
-use clippy_utils::{get_parent_node, is_lint_allowed};
+use clippy_utils::{get_parent_node, should_skip_lint};
 use hir::HirId;
 use rustc_data_structures::sync::Lrc;
 use rustc_hir as hir;
@@ -113,7 +113,7 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
     fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
         if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
             && !in_external_macro(cx.tcx.sess, block.span)
-            && !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_BLOCKS, block.hir_id)
+            && !should_skip_lint(cx, UNDOCUMENTED_UNSAFE_BLOCKS, block.hir_id)
             && !is_unsafe_from_proc_macro(cx, block.span)
             && !block_has_safety_comment(cx, block.span)
             && !block_parents_have_safety_comment(
@@ -141,7 +141,7 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
         }
 
         if let Some(tail) = block.expr
-            && !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, tail.hir_id)
+            && !should_skip_lint(cx, UNNECESSARY_SAFETY_COMMENT, tail.hir_id)
             && !in_external_macro(cx.tcx.sess, tail.span)
             && let HasSafetyComment::Yes(pos) = stmt_has_safety_comment(cx, tail.span, tail.hir_id)
             && let Some(help_span) = expr_has_unnecessary_safety_comment(cx, tail, pos)
@@ -164,7 +164,7 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
         else {
             return;
         };
-        if !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, stmt.hir_id)
+        if !should_skip_lint(cx, UNNECESSARY_SAFETY_COMMENT, stmt.hir_id)
             && !in_external_macro(cx.tcx.sess, stmt.span)
             && let HasSafetyComment::Yes(pos) = stmt_has_safety_comment(cx, stmt.span, stmt.hir_id)
             && let Some(help_span) = expr_has_unnecessary_safety_comment(cx, expr, pos)
@@ -201,7 +201,7 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
         match (&item.kind, item_has_safety_comment) {
             // lint unsafe impl without safety comment
             (hir::ItemKind::Impl(impl_), HasSafetyComment::No) if impl_.unsafety == hir::Unsafety::Unsafe => {
-                if !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_BLOCKS, item.hir_id())
+                if !should_skip_lint(cx, UNDOCUMENTED_UNSAFE_BLOCKS, item.hir_id())
                     && !is_unsafe_from_proc_macro(cx, item.span)
                 {
                     let source_map = cx.tcx.sess.source_map();
@@ -223,7 +223,7 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
             },
             // lint safe impl with unnecessary safety comment
             (hir::ItemKind::Impl(impl_), HasSafetyComment::Yes(pos)) if impl_.unsafety == hir::Unsafety::Normal => {
-                if !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, item.hir_id()) {
+                if !should_skip_lint(cx, UNNECESSARY_SAFETY_COMMENT, item.hir_id()) {
                     let (span, help_span) = mk_spans(pos);
 
                     span_lint_and_help(
@@ -239,7 +239,7 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
             (hir::ItemKind::Impl(_), _) => {},
             // const and static items only need a safety comment if their body is an unsafe block, lint otherwise
             (&hir::ItemKind::Const(.., body) | &hir::ItemKind::Static(.., body), HasSafetyComment::Yes(pos)) => {
-                if !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, body.hir_id) {
+                if !should_skip_lint(cx, UNNECESSARY_SAFETY_COMMENT, body.hir_id) {
                     let body = cx.tcx.hir().body(body);
                     if !matches!(
                         body.value.kind, hir::ExprKind::Block(block, _)
@@ -261,7 +261,7 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
             // Aside from unsafe impls and consts/statics with an unsafe block, items in general
             // do not have safety invariants that need to be documented, so lint those.
             (_, HasSafetyComment::Yes(pos)) => {
-                if !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, item.hir_id()) {
+                if !should_skip_lint(cx, UNNECESSARY_SAFETY_COMMENT, item.hir_id()) {
                     let (span, help_span) = mk_spans(pos);
 
                     span_lint_and_help(
@@ -423,6 +423,74 @@ fn block_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
     ) || span_has_safety_comment(cx, span)
 }
 
+/// Returns the text of the safety comment on the lines immediately preceding the unsafe block, if
+/// there is one.
+pub(crate) fn block_safety_comment(cx: &LateContext<'_>, span: Span) -> Option<String> {
+    match span_from_macro_expansion_has_safety_comment(cx, span) {
+        HasSafetyComment::Yes(pos) => safety_comment_text(cx, pos, span),
+        HasSafetyComment::No => None,
+        HasSafetyComment::Maybe => match span_safety_comment(cx, span) {
+            HasSafetyComment::Yes(pos) => safety_comment_text(cx, pos, span),
+            HasSafetyComment::No | HasSafetyComment::Maybe => None,
+        },
+    }
+}
+
+/// Returns the text of the safety comment on the lines immediately preceding the item, if there is
+/// one.
+pub(crate) fn item_safety_comment(cx: &LateContext<'_>, item: &hir::Item<'_>) -> Option<String> {
+    match item_has_safety_comment(cx, item) {
+        HasSafetyComment::Yes(pos) => safety_comment_text(cx, pos, item.span),
+        HasSafetyComment::No | HasSafetyComment::Maybe => None,
+    }
+}
+
+/// Returns the text of the safety comment on the lines immediately preceding an item of an impl or
+/// a trait, if there is one. `parent_span` is the span of the impl or the trait.
+pub(crate) fn assoc_item_safety_comment(cx: &LateContext<'_>, span: Span, parent_span: Span) -> Option<String> {
+    let comment_pos = match span_from_macro_expansion_has_safety_comment(cx, span) {
+        HasSafetyComment::Yes(pos) => pos,
+        HasSafetyComment::No => return None,
+        HasSafetyComment::Maybe => {
+            // Get the text from the start of the impl or the trait to the item.
+            //     impl Foo { fn bar() {} unsafe fn baz() {} }
+            //     ^----------------------^
+            let source_map = cx.sess().source_map();
+            let item_line = source_map.lookup_line(span.lo()).ok()?;
+            let parent_span = walk_span_to_context(parent_span, SyntaxContext::root())?;
+            let parent_line = source_map.lookup_line(parent_span.lo()).ok()?;
+            if !Lrc::ptr_eq(&item_line.sf, &parent_line.sf) || parent_line.line >= item_line.line {
+                return None;
+            }
+            let src = item_line.sf.src.as_deref()?;
+            item_line.sf.lines(|lines| {
+                text_has_safety_comment(
+                    src,
+                    &lines[parent_line.line + 1..=item_line.line],
+                    item_line.sf.start_pos,
+                )
+            })?
+        },
+    };
+    safety_comment_text(cx, comment_pos, span)
+}
+
+/// Gets the text from the start of the safety comment at `comment_pos` up to the line containing
+/// the start of `span`.
+fn safety_comment_text(cx: &LateContext<'_>, comment_pos: BytePos, span: Span) -> Option<String> {
+    let source_map = cx.sess().source_map();
+    let line = source_map.lookup_line(span.lo()).ok()?;
+    let line_start = line.sf.line_bounds(line.line).start;
+    if line_start < comment_pos {
+        return None;
+    }
+    let comment_span = Span::new(comment_pos, line_start, SyntaxContext::root(), None);
+    source_map
+        .span_to_snippet(comment_span)
+        .ok()
+        .map(|text| text.trim_end().to_string())
+}
+
 fn include_attrs_in_span(cx: &LateContext<'_>, hir_id: HirId, span: Span) -> Span {
     span.to(cx
         .tcx
@@ -642,6 +710,10 @@ fn get_body_search_span(cx: &LateContext<'_>) -> Option<Span> {
 }
 
 fn span_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
+    !matches!(span_safety_comment(cx, span), HasSafetyComment::No)
+}
+
+fn span_safety_comment(cx: &LateContext<'_>, span: Span) -> HasSafetyComment {
     let source_map = cx.sess().source_map();
     let ctxt = span.ctxt();
     if ctxt.is_root() && let Some(search_span) = get_body_search_span(cx) {
@@ -655,18 +727,24 @@ fn span_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
             //     fn foo() { some_stuff; unsafe { stuff }; other_stuff; }
             //              ^-------------^
             unsafe_line.sf.lines(|lines| {
-                body_line.line < unsafe_line.line && text_has_safety_comment(
-                    src,
-                    &lines[body_line.line + 1..=unsafe_line.line],
-                    unsafe_line.sf.start_pos,
-                ).is_some()
+                if body_line.line < unsafe_line.line
+                    && let Some(pos) = text_has_safety_comment(
+                        src,
+                        &lines[body_line.line + 1..=unsafe_line.line],
+                        unsafe_line.sf.start_pos,
+                    )
+                {
+                    HasSafetyComment::Yes(pos)
+                } else {
+                    HasSafetyComment::No
+                }
             })
         } else {
             // Problem getting source text. Pretend a comment was found.
-            true
+            HasSafetyComment::Maybe
         }
     } else {
-        false
+        HasSafetyComment::No
     }
 }
 
@@ -0,0 +1,358 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::implements_trait_with_env;
+use clippy_utils::visitors::for_each_local_use_after_expr;
+use clippy_utils::{get_parent_expr, get_parent_node, is_path_diagnostic_item};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::{BinOpKind, Expr, ExprKind, Item, ItemKind, Local, Mutability, Node, Pat, PatKind, UnOp};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::adjustment::Adjust;
+use rustc_middle::ty::{self, IntTy, Ty, UintTy};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Mutex` and `RwLock` fields, possibly in an `Arc`, whose value is only read, or
+    /// is only loaded, stored or updated with a single arithmetic or bitwise operation at a time,
+    /// looking at every use of the field in the crate.
+    /// Values which are not `Sync` or have interior mutability are only linted in the second case.
+    ///
+    /// ### Why is this bad?
+    /// A value that is never modified doesn't need a lock, and can be shared through an `Arc` if
+    /// needed. A lock only used for single operations on an integer or a `bool` can be replaced by
+    /// the matching atomic, which is leaner and faster.
+    ///
+    /// ### Known problems
+    /// Fields that can be used by other crates, and fields whose lock or guard is used in another
+    /// way, for example passed to a `Condvar`, are ignored. The lock might also be used to order
+    /// operations on other values, which atomics with relaxed ordering don't do.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::sync::Mutex;
+    /// struct Stats {
+    ///     requests: Mutex<usize>,
+    /// }
+    ///
+    /// impl Stats {
+    ///     fn record(&self) {
+    ///         *self.requests.lock().unwrap() += 1;
+    ///     }
+    ///
+    ///     fn requests(&self) -> usize {
+    ///         *self.requests.lock().unwrap()
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// struct Stats {
+    ///     requests: AtomicUsize,
+    /// }
+    ///
+    /// impl Stats {
+    ///     fn record(&self) {
+    ///         self.requests.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    ///
+    ///     fn requests(&self) -> usize {
+    ///         self.requests.load(Ordering::Relaxed)
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.74.0"]
+    pub UNNECESSARY_LOCK,
+    nursery,
+    "`Mutex` or `RwLock` fields whose uses don't need a lock, or could use an atomic"
+}
+
+#[derive(Default)]
+pub struct UnnecessaryLock {
+    /// The `Mutex` and `RwLock` fields of the crate.
+    fields: FxIndexMap<LocalDefId, Span>,
+    usages: FxHashMap<DefId, Usage>,
+}
+
+impl_lint_pass!(UnnecessaryLock => [UNNECESSARY_LOCK]);
+
+#[derive(Default)]
+struct Usage {
+    locks: usize,
+    /// Whether the value is modified through one of the locks.
+    writes: bool,
+    /// Whether one of the locks is used for something else than a single load, store or
+    /// read-modify-write operation.
+    non_atomic: bool,
+    /// Whether the field, one of its locks or guards is used in a way that isn't understood.
+    escapes: bool,
+}
+
+/// How a lock guard is used.
+#[derive(Clone, Copy, PartialEq)]
+enum Access {
+    /// The value is copied out.
+    Load,
+    /// The value is overwritten.
+    Store,
+    /// The value is updated with a compound assignment.
+    Update(BinOpKind),
+    /// The value is borrowed immutably.
+    Read,
+    /// The value is borrowed mutably.
+    Write,
+    /// The guard is dropped explicitly.
+    Drop,
+    Unknown,
+}
+
+impl<'tcx> LateLintPass<'tcx> for UnnecessaryLock {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Struct(ref data, _) = item.kind
+            && !item.span.from_expansion()
+        {
+            for field in data.fields() {
+                if cx.effective_visibilities.is_exported(field.def_id) {
+                    continue;
+                }
+                if lock_ty(cx, cx.tcx.type_of(field.def_id).instantiate_identity()).is_some() {
+                    self.fields.insert(field.def_id, field.span);
+                }
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Field(base, ident) = expr.kind
+            && let ty::Adt(adt, _) = cx.typeck_results().expr_ty_adjusted(base).peel_refs().kind()
+            && adt.is_struct()
+            && let Some(field) = adt.non_enum_variant().fields.iter().find(|field| field.name == ident.name)
+            && field.did.is_local()
+            && lock_ty(cx, cx.typeck_results().expr_ty(expr)).is_some()
+        {
+            let usage = self.usages.entry(field.did).or_default();
+            match lock_guard(cx, expr) {
+                Some(guard) => {
+                    usage.locks += 1;
+                    let mut uses = 0;
+                    for access in guard_accesses(cx, guard) {
+                        match access {
+                            Access::Drop => continue,
+                            Access::Unknown => usage.escapes = true,
+                            Access::Load | Access::Store => {},
+                            // `fetch_add`, `fetch_sub`, `fetch_and`, `fetch_or` and `fetch_xor`
+                            Access::Update(op) => {
+                                usage.non_atomic |= !matches!(
+                                    op,
+                                    BinOpKind::Add
+                                        | BinOpKind::Sub
+                                        | BinOpKind::BitAnd
+                                        | BinOpKind::BitOr
+                                        | BinOpKind::BitXor
+                                );
+                            },
+                            Access::Read | Access::Write => usage.non_atomic = true,
+                        }
+                        usage.writes |= matches!(access, Access::Store | Access::Update(_) | Access::Write);
+                        uses += 1;
+                    }
+                    // Several operations under the same lock are not atomic anymore
+                    usage.non_atomic |= uses != 1;
+                },
+                None => {
+                    // Checking whether the lock is poisoned doesn't use the value
+                    if !matches!(
+                        get_parent_expr(cx, expr).map(|e| &e.kind),
+                        Some(ExprKind::MethodCall(path, recv, [], _))
+                            if recv.hir_id == expr.hir_id && path.ident.as_str() == "is_poisoned"
+                    ) {
+                        usage.escapes = true;
+                    }
+                },
+            }
+        }
+    }
+
+    fn check_pat(&mut self, cx: &LateContext<'tcx>, pat: &'tcx Pat<'_>) {
+        // Fields moved or borrowed out of their struct can be used anywhere
+        if let PatKind::Struct(_, fields, _) = pat.kind
+            && let ty::Adt(adt, _) = cx.typeck_results().pat_ty(pat).kind()
+        {
+            for field in fields {
+                if let Some(field) = adt.all_fields().find(|f| f.name == field.ident.name) {
+                    self.usages.entry(field.did).or_default().escapes = true;
+                }
+            }
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        for (&field, &span) in &self.fields {
+            let Some(usage) = self.usages.get(&field.to_def_id()) else {
+                continue;
+            };
+            let Some((lock, inner)) = lock_ty(cx, cx.tcx.type_of(field).instantiate_identity()) else {
+                continue;
+            };
+            if usage.escapes || usage.locks == 0 {
+                continue;
+            }
+            // Shared without a lock, the value must be `Sync`, and reading it must not be able to
+            // modify it, e.g. through a `RefCell`
+            let param_env = cx.tcx.param_env(cx.tcx.parent(field.to_def_id()));
+            if !usage.writes
+                && let Some(sync) = cx.tcx.get_diagnostic_item(sym::Sync)
+                && implements_trait_with_env(cx.tcx, param_env, inner, sync, &[])
+                && inner.is_freeze(cx.tcx, param_env)
+            {
+                span_lint_and_help(
+                    cx,
+                    UNNECESSARY_LOCK,
+                    span,
+                    &format!("this `{lock}` is only locked to read its value"),
+                    None,
+                    &format!(
+                        "the value is never modified: consider using `{inner}` without a lock, or `Arc<{inner}>` to share it"
+                    ),
+                );
+            } else if !usage.non_atomic
+                && let Some(atomic) = atomic_ty(inner)
+            {
+                span_lint_and_help(
+                    cx,
+                    UNNECESSARY_LOCK,
+                    span,
+                    &format!("this `{lock}` is only used for single operations on a `{inner}`"),
+                    None,
+                    &format!("consider using `std::sync::atomic::{atomic}`"),
+                );
+            }
+        }
+    }
+}
+
+/// Returns the name and the content of a `Mutex` or `RwLock`, possibly in an `Arc`.
+fn lock_ty<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> Option<(&'static str, Ty<'tcx>)> {
+    let ty = match ty.kind() {
+        ty::Adt(adt, args) if cx.tcx.is_diagnostic_item(sym::Arc, adt.did()) => args.type_at(0),
+        _ => ty,
+    };
+    if let ty::Adt(adt, args) = ty.kind() {
+        if cx.tcx.is_diagnostic_item(sym::Mutex, adt.did()) {
+            return Some(("Mutex", args.type_at(0)));
+        }
+        if cx.tcx.is_diagnostic_item(sym::RwLock, adt.did()) {
+            return Some(("RwLock", args.type_at(0)));
+        }
+    }
+    None
+}
+
+fn atomic_ty(ty: Ty<'_>) -> Option<&'static str> {
+    Some(match ty.kind() {
+        ty::Bool => "AtomicBool",
+        ty::Uint(UintTy::U8) => "AtomicU8",
+        ty::Uint(UintTy::U16) => "AtomicU16",
+        ty::Uint(UintTy::U32) => "AtomicU32",
+        ty::Uint(UintTy::U64) => "AtomicU64",
+        ty::Uint(UintTy::Usize) => "AtomicUsize",
+        ty::Int(IntTy::I8) => "AtomicI8",
+        ty::Int(IntTy::I16) => "AtomicI16",
+        ty::Int(IntTy::I32) => "AtomicI32",
+        ty::Int(IntTy::I64) => "AtomicI64",
+        ty::Int(IntTy::Isize) => "AtomicIsize",
+        _ => return None,
+    })
+}
+
+/// Returns the guard of `lock.lock().unwrap()`, `lock.read().unwrap()` or `lock.write().unwrap()`.
+fn lock_guard<'tcx>(cx: &LateContext<'tcx>, lock: &'tcx Expr<'tcx>) -> Option<&'tcx Expr<'tcx>> {
+    let locked = get_parent_expr(cx, lock)?;
+    let guard = get_parent_expr(cx, locked)?;
+    if let ExprKind::MethodCall(lock_method, recv, [], _) = locked.kind
+        && recv.hir_id == lock.hir_id
+        && matches!(lock_method.ident.as_str(), "lock" | "read" | "write")
+        && let ExprKind::MethodCall(unwrap_method, recv, _, _) = guard.kind
+        && recv.hir_id == locked.hir_id
+        && matches!(unwrap_method.ident.as_str(), "unwrap" | "expect" | "unwrap_or_else")
+    {
+        Some(guard)
+    } else {
+        None
+    }
+}
+
+/// Returns how the guard is used, once for each use.
+fn guard_accesses<'tcx>(cx: &LateContext<'tcx>, guard: &'tcx Expr<'tcx>) -> Vec<Access> {
+    if let Some(Node::Local(Local {
+        pat, init: Some(init), ..
+    })) = get_parent_node(cx.tcx, guard.hir_id)
+        && init.hir_id == guard.hir_id
+    {
+        let PatKind::Binding(_, id, _, None) = pat.kind else {
+            return vec![Access::Unknown];
+        };
+        let mut accesses = Vec::new();
+        let _: ControlFlow<()> = for_each_local_use_after_expr(cx, id, guard.hir_id, |e| {
+            accesses.push(guard_access(cx, e));
+            ControlFlow::Continue(())
+        });
+        // A guard which is never used protects something else
+        if accesses.iter().all(|&access| access == Access::Drop) {
+            return vec![Access::Unknown];
+        }
+        return accesses;
+    }
+    vec![guard_access(cx, guard)]
+}
+
+fn guard_access<'tcx>(cx: &LateContext<'tcx>, guard: &'tcx Expr<'tcx>) -> Access {
+    let Some(parent) = get_parent_expr(cx, guard) else {
+        return Access::Unknown;
+    };
+    match parent.kind {
+        ExprKind::Unary(UnOp::Deref, _) => deref_access(cx, parent),
+        ExprKind::AddrOf(_, Mutability::Not, _) => Access::Read,
+        ExprKind::AddrOf(_, Mutability::Mut, _) => Access::Write,
+        ExprKind::Call(func, [arg])
+            if arg.hir_id == guard.hir_id && is_path_diagnostic_item(cx, func, sym::mem_drop) =>
+        {
+            Access::Drop
+        },
+        // Auto-deref of the guard, for method calls, field accesses or coercions
+        _ => match cx
+            .typeck_results()
+            .expr_adjustments(guard)
+            .iter()
+            .find_map(|adjustment| match adjustment.kind {
+                Adjust::Deref(Some(deref)) => Some(deref.mutbl),
+                _ => None,
+            }) {
+            Some(Mutability::Mut) => Access::Write,
+            Some(Mutability::Not) => Access::Read,
+            None => Access::Unknown,
+        },
+    }
+}
+
+fn deref_access<'tcx>(cx: &LateContext<'tcx>, deref: &'tcx Expr<'tcx>) -> Access {
+    let parent = get_parent_expr(cx, deref).map(|e| e.kind);
+    match parent {
+        Some(ExprKind::Assign(lhs, ..)) if lhs.hir_id == deref.hir_id => Access::Store,
+        Some(ExprKind::AssignOp(op, lhs, _)) if lhs.hir_id == deref.hir_id => Access::Update(op.node),
+        _ if cx
+            .typeck_results()
+            .type_dependent_def_id(deref.hir_id)
+            .map_or(false, |did| {
+                cx.tcx.trait_of_item(did) == cx.tcx.lang_items().deref_mut_trait()
+            }) =>
+        {
+            Access::Write
+        },
+        Some(ExprKind::AddrOf(..)) => Access::Read,
+        _ if !cx.typeck_results().expr_adjustments(deref).is_empty() => Access::Read,
+        _ => Access::Load,
+    }
+}
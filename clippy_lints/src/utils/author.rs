@@ -1,19 +1,26 @@
 //! A group of attributes that can be attached to Rust code in order
 //! to generate a clippy lint detecting said code automatically.
 
+use clippy_utils::source::snippet_opt;
 use clippy_utils::{get_attr, higher};
+use itertools::Itertools;
 use rustc_ast::ast::{LitFloatType, LitKind};
 use rustc_ast::LitIntType;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
 use rustc_hir::{
     ArrayLen, BindingAnnotation, Closure, ExprKind, FnRetTy, HirId, Lit, PatKind, QPath, StmtKind, TyKind,
+    CRATE_OWNER_ID,
 };
 use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_session::{declare_lint_pass, declare_tool_lint};
 use rustc_span::symbol::{Ident, Symbol};
-use std::cell::Cell;
-use std::fmt::{Display, Formatter, Write as _};
+use rustc_span::{sym, Span};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter, Write as _};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -47,6 +54,11 @@ declare_clippy_lint! {
     ///     // report your lint here
     /// }
     /// ```
+    ///
+    /// Giving the attribute the name of a new lint, e.g. `#[clippy::author = "my_lint"]`, instead
+    /// produces a complete lint module declaring `MY_LINT` and a `tests/ui/my_lint.rs` file using
+    /// the surrounding item as the test case. Paths are matched by what they resolve to rather than
+    /// by their segments. The output can be passed to `cargo dev new_lint --seed`.
     pub LINT_AUTHOR,
     internal_warn,
     "helper for writing lints"
@@ -56,8 +68,8 @@ declare_lint_pass!(Author => [LINT_AUTHOR]);
 
 /// Writes a line of output with indentation added
 macro_rules! out {
-    ($($t:tt)*) => {
-        println!("    {}", format_args!($($t)*))
+    ($self:ident, $($t:tt)*) => {
+        $self.line(format_args!("    {}", format_args!($($t)*)))
     };
 }
 
@@ -95,9 +107,9 @@ macro_rules! field {
 macro_rules! chain {
     ($self:ident, $($t:tt)*) => {
         if $self.first.take() {
-            println!("if {}", format_args!($($t)*));
+            $self.line(format_args!("if {}", format_args!($($t)*)));
         } else {
-            println!("    && {}", format_args!($($t)*));
+            $self.line(format_args!("    && {}", format_args!($($t)*)));
         }
     }
 }
@@ -116,23 +128,23 @@ impl<'tcx> LateLintPass<'tcx> for Author {
     }
 
     fn check_arm(&mut self, cx: &LateContext<'tcx>, arm: &'tcx hir::Arm<'_>) {
-        check_node(cx, arm.hir_id, |v| {
+        check_node(cx, arm.hir_id, Root::Arm, |v| {
             v.arm(&v.bind("arm", arm));
         });
     }
 
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
-        check_node(cx, expr.hir_id, |v| {
+        check_node(cx, expr.hir_id, Root::Expr, |v| {
             v.expr(&v.bind("expr", expr));
         });
     }
 
     fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx hir::Stmt<'_>) {
         match stmt.kind {
-            StmtKind::Expr(e) | StmtKind::Semi(e) if has_attr(cx, e.hir_id) => return,
+            StmtKind::Expr(e) | StmtKind::Semi(e) if author_mode(cx, e.hir_id).is_some() => return,
             _ => {},
         }
-        check_node(cx, stmt.hir_id, |v| {
+        check_node(cx, stmt.hir_id, Root::Stmt, |v| {
             v.stmt(&v.bind("stmt", stmt));
         });
     }
@@ -141,18 +153,214 @@ impl<'tcx> LateLintPass<'tcx> for Author {
 fn check_item(cx: &LateContext<'_>, hir_id: HirId) {
     let hir = cx.tcx.hir();
     if let Some(body_id) = hir.maybe_body_owned_by(hir_id.expect_owner().def_id) {
-        check_node(cx, hir_id, |v| {
+        check_node(cx, hir_id, Root::Body, |v| {
             v.expr(&v.bind("expr", hir.body(body_id).value));
         });
     }
 }
 
-fn check_node(cx: &LateContext<'_>, hir_id: HirId, f: impl Fn(&PrintVisitor<'_, '_>)) {
-    if has_attr(cx, hir_id) {
-        f(&PrintVisitor::new(cx));
-        println!("{{");
-        println!("    // report your lint here");
-        println!("}}");
+/// What the `#[clippy::author]` attribute asks for.
+enum Mode {
+    /// Print the if-let chain matching the node.
+    Chain,
+    /// Print a lint module with the given name detecting the node, along with its test.
+    Lint(Symbol),
+}
+
+/// The kind of node the attribute is attached to, which determines the lint pass method the
+/// generated code is placed in.
+#[derive(Clone, Copy)]
+enum Root {
+    Arm,
+    Body,
+    Expr,
+    Stmt,
+}
+
+fn check_node(cx: &LateContext<'_>, hir_id: HirId, root: Root, f: impl Fn(&PrintVisitor<'_, '_>)) {
+    let Some(mode) = author_mode(cx, hir_id) else {
+        return;
+    };
+    let v = PrintVisitor::new(cx, matches!(mode, Mode::Lint(_)));
+    f(&v);
+    let chain = v.output.into_inner();
+    match mode {
+        Mode::Chain => {
+            print!("{chain}");
+            println!("{{");
+            println!("    // report your lint here");
+            println!("}}");
+        },
+        Mode::Lint(name) => print_lint(cx, hir_id, name.as_str(), root, &chain),
+    }
+}
+
+/// Names that may be used by the generated code, along with where to import them from.
+const IMPORTS: &[(&str, &str)] = &[
+    ("higher::", "clippy_utils::higher"),
+    ("is_res_lang_ctor(", "clippy_utils::is_res_lang_ctor"),
+    ("match_def_path(", "clippy_utils::match_def_path"),
+    ("match_qpath(", "clippy_utils::match_qpath"),
+    ("FloatTy::", "rustc_ast::ast::FloatTy"),
+    ("IntTy::", "rustc_ast::ast::IntTy"),
+    ("LitFloatType::", "rustc_ast::ast::LitFloatType"),
+    ("LitIntType::", "rustc_ast::ast::LitIntType"),
+    ("LitKind::", "rustc_ast::ast::LitKind"),
+    ("UintTy::", "rustc_ast::ast::UintTy"),
+    ("ArrayLen::", "rustc_hir::ArrayLen"),
+    ("BinOpKind::", "rustc_hir::BinOpKind"),
+    ("BindingAnnotation::", "rustc_hir::BindingAnnotation"),
+    ("BorrowKind::", "rustc_hir::BorrowKind"),
+    ("CaptureBy::", "rustc_hir::CaptureBy"),
+    ("ExprKind::", "rustc_hir::ExprKind"),
+    ("FnRetTy::", "rustc_hir::FnRetTy"),
+    ("Guard::", "rustc_hir::Guard"),
+    ("LangItem::", "rustc_hir::LangItem"),
+    ("LoopSource::", "rustc_hir::LoopSource"),
+    ("MatchSource::", "rustc_hir::MatchSource"),
+    ("Movability::", "rustc_hir::Movability"),
+    ("Mutability::", "rustc_hir::Mutability"),
+    ("PatKind::", "rustc_hir::PatKind"),
+    ("QPath::", "rustc_hir::QPath"),
+    ("RangeEnd::", "rustc_hir::RangeEnd"),
+    ("StmtKind::", "rustc_hir::StmtKind"),
+    ("TyKind::", "rustc_hir::TyKind"),
+    ("UnOp::", "rustc_hir::UnOp"),
+    ("YieldSource::", "rustc_hir::YieldSource"),
+    ("sym::", "rustc_span::sym"),
+];
+
+/// Prints a lint module named `name` whose pass reports the nodes matched by `chain`, followed by a
+/// test file for it.
+fn print_lint(cx: &LateContext<'_>, hir_id: HirId, name: &str, root: Root, chain: &str) {
+    let name_upper = name.to_uppercase();
+    let name_camel: String = name
+        .split('_')
+        .map(|s| s.get(..1).map_or(String::new(), |first| first.to_uppercase() + &s[1..]))
+        .collect();
+    let (method, param, param_ty) = match root {
+        Root::Arm => ("check_arm", "arm", "Arm"),
+        Root::Body => ("check_body", "body", "Body"),
+        Root::Expr => ("check_expr", "expr", "Expr"),
+        Root::Stmt => ("check_stmt", "stmt", "Stmt"),
+    };
+    let span_name = if let Root::Body = root { "expr" } else { param };
+
+    let mut imports: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let used = IMPORTS
+        .iter()
+        .filter(|(used, _)| chain.contains(used))
+        .map(|(_, path)| *path);
+    for path in used.chain([
+        "clippy_utils::diagnostics::span_lint",
+        "rustc_lint::LateContext",
+        "rustc_lint::LateLintPass",
+        "rustc_session::declare_lint_pass",
+        "rustc_session::declare_tool_lint",
+    ]) {
+        let (module, name) = path.rsplit_once("::").unwrap();
+        imports.entry(module).or_default().push(name);
+    }
+    imports.entry("rustc_hir").or_default().push(param_ty);
+
+    let example = source_without_author(cx, cx.tcx.hir().span(hir_id), hir_id);
+    let version = env!("CARGO_PKG_VERSION").split('.').nth(2).unwrap_or_default();
+
+    println!("// clippy_lints/src/{name}.rs");
+    for (module, mut names) in imports {
+        names.sort_unstable();
+        if let [name] = *names {
+            println!("use {module}::{name};");
+        } else {
+            println!("use {module}::{{{}}};", names.join(", "));
+        }
+    }
+    println!();
+    println!("declare_clippy_lint! {{");
+    println!("    /// ### What it does");
+    println!("    ///");
+    println!("    /// ### Why is this bad?");
+    println!("    ///");
+    println!("    /// ### Example");
+    println!("    /// ```rust");
+    for line in example.lines() {
+        println!("    /// {line}");
+    }
+    println!("    /// ```");
+    println!("    /// Use instead:");
+    println!("    /// ```rust");
+    println!("    /// // example code which does not raise clippy warning");
+    println!("    /// ```");
+    println!("    #[clippy::version = \"1.{version}.0\"]");
+    println!("    pub {name_upper},");
+    println!("    nursery,");
+    println!("    \"default lint description\"");
+    println!("}}");
+    println!();
+    println!("declare_lint_pass!({name_camel} => [{name_upper}]);");
+    println!();
+    println!("impl<'tcx> LateLintPass<'tcx> for {name_camel} {{");
+    println!("    fn {method}(&mut self, cx: &LateContext<'tcx>, {param}: &'tcx {param_ty}<'_>) {{");
+    if let Root::Body = root {
+        println!("        let expr = body.value;");
+    }
+    for line in chain.lines() {
+        println!("        {line}");
+    }
+    println!("        {{");
+    println!("            span_lint(cx, {name_upper}, {span_name}.span, \"default lint description\");");
+    println!("        }}");
+    println!("    }}");
+    println!("}}");
+    println!();
+
+    // Use the whole surrounding item as the test case
+    let hir = cx.tcx.hir();
+    let mut owner = hir_id.owner;
+    loop {
+        let parent = hir.get_parent_item(owner.into());
+        if parent == CRATE_OWNER_ID || cx.tcx.def_kind(parent) == DefKind::Mod {
+            break;
+        }
+        owner = parent;
+    }
+    let item = source_without_author(cx, hir.span_with_body(owner.into()), hir_id);
+    println!("// tests/ui/{name}.rs");
+    println!("#![warn(clippy::{name})]");
+    println!();
+    println!("{item}");
+    if cx
+        .tcx
+        .opt_item_name(owner.to_def_id())
+        .map_or(true, |name| name != sym::main)
+    {
+        println!();
+        println!("fn main() {{}}");
+    }
+}
+
+/// Gets the source of `span`, leaving out the `#[clippy::author]` attribute of `hir_id`.
+fn source_without_author(cx: &LateContext<'_>, span: Span, hir_id: HirId) -> String {
+    let Some(source) = snippet_opt(cx, span) else {
+        return "..".to_string();
+    };
+    let Some(attr) = get_attr(cx.sess(), cx.tcx.hir().attrs(hir_id), "author").next() else {
+        return source;
+    };
+    if !span.contains(attr.span) {
+        return source;
+    }
+    let start = (attr.span.lo() - span.lo()).0 as usize;
+    let end = (attr.span.hi() - span.lo()).0 as usize;
+    let (Some(before), Some(after)) = (source.get(..start), source.get(end..)) else {
+        return source;
+    };
+    // Remove the line of the attribute if it's on its own
+    match (before.rsplit_once('\n'), after.split_once('\n')) {
+        (Some((before, indent)), Some((rest, after))) if indent.trim().is_empty() && rest.trim().is_empty() => {
+            format!("{before}\n{after}")
+        },
+        _ => format!("{before}{}", after.trim_start()),
     }
 }
 
@@ -199,18 +407,28 @@ struct PrintVisitor<'a, 'tcx> {
     ids: Cell<FxHashMap<&'static str, u32>>,
     /// Currently at the first condition in the if chain
     first: Cell<bool>,
+    /// Whether paths are matched by what they resolve to instead of by their segments
+    resolve: bool,
+    /// The lines of the if chain
+    output: RefCell<String>,
 }
 
 #[allow(clippy::unused_self)]
 impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
-    fn new(cx: &'a LateContext<'tcx>) -> Self {
+    fn new(cx: &'a LateContext<'tcx>, resolve: bool) -> Self {
         Self {
             cx,
             ids: Cell::default(),
             first: Cell::new(true),
+            resolve,
+            output: RefCell::default(),
         }
     }
 
+    fn line(&self, line: fmt::Arguments<'_>) {
+        writeln!(self.output.borrow_mut(), "{line}").unwrap();
+    }
+
     fn next(&self, s: &'static str) -> String {
         let mut ids = self.ids.take();
         let out = match *ids.entry(s).and_modify(|n| *n += 1).or_default() {
@@ -273,6 +491,57 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
         }
     }
 
+    /// Matches the item `qpath` resolves to, where `owner` is the expression or pattern containing
+    /// it. Falls back to matching the segments of the path if it doesn't resolve to an item or
+    /// paths aren't resolved.
+    fn qpath_res(&self, qpath: &Binding<&QPath<'_>>, owner: &Binding<HirId>) {
+        if self.resolve && !matches!(qpath.value, QPath::LangItem(..)) {
+            let tcx = self.cx.tcx;
+            match self.cx.qpath_res(qpath.value, owner.value) {
+                Res::Def(DefKind::Ctor(..), ctor_id)
+                    if let Some((lang_item, _)) =
+                        tcx.lang_items().iter().find(|&(_, id)| id == tcx.parent(ctor_id)) =>
+                {
+                    chain!(
+                        self,
+                        "is_res_lang_ctor(cx, cx.qpath_res({qpath}, {owner}), LangItem::{lang_item:?})"
+                    );
+                    return;
+                },
+                Res::Def(_, def_id) => {
+                    let def_id = &self.bind("def_id", def_id);
+                    chain!(self, "let Some({def_id}) = cx.qpath_res({qpath}, {owner}).opt_def_id()");
+                    self.def_id(def_id);
+                    return;
+                },
+                _ => {},
+            }
+        }
+        self.qpath(qpath);
+    }
+
+    fn def_id(&self, def_id: &Binding<DefId>) {
+        if let Some(name) = self.cx.tcx.get_diagnostic_name(def_id.value) {
+            chain!(self, "cx.tcx.is_diagnostic_item(sym::{name}, {def_id})");
+        } else {
+            let path = self
+                .cx
+                .get_def_path(def_id.value)
+                .iter()
+                .map(|segment| format!("{:?}", segment.as_str()))
+                .join(", ");
+            chain!(self, "match_def_path(cx, {def_id}, &[{path}])");
+        }
+    }
+
+    /// Creates a binding for the `hir_id` of the node bound to `node`.
+    fn hir_id<T>(&self, node: &Binding<T>, hir_id: HirId) -> Binding<HirId> {
+        Binding {
+            name: format!("{node}.hir_id"),
+            value: hir_id,
+        }
+    }
+
     fn lit(&self, lit: &Binding<&Lit>) {
         let kind = |kind| chain!(self, "let LitKind::{kind} = {lit}.node");
         macro_rules! kind {
@@ -415,6 +684,16 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
                 bind!(self, method_name, receiver, args);
                 kind!("MethodCall({method_name}, {receiver}, {args}, _)");
                 self.ident(field!(method_name.ident));
+                if self.resolve
+                    && let Some(def_id) = self.cx.typeck_results().type_dependent_def_id(expr.value.hir_id)
+                {
+                    let def_id = &self.bind("def_id", def_id);
+                    chain!(
+                        self,
+                        "let Some({def_id}) = cx.typeck_results().type_dependent_def_id({expr}.hir_id)"
+                    );
+                    self.def_id(def_id);
+                }
                 self.expr(receiver);
                 self.slice(args, |e| self.expr(e));
             },
@@ -535,7 +814,7 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
             ExprKind::Path(ref qpath) => {
                 bind!(self, qpath);
                 kind!("Path(ref {qpath})");
-                self.qpath(qpath);
+                self.qpath_res(qpath, &self.hir_id(expr, expr.value.hir_id));
             },
             ExprKind::AddrOf(kind, mutability, inner) => {
                 bind!(self, inner);
@@ -566,7 +845,10 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
             },
             ExprKind::InlineAsm(_) => {
                 kind!("InlineAsm(_)");
-                out!("// unimplemented: `ExprKind::InlineAsm` is not further destructured at the moment");
+                out!(
+                    self,
+                    "// unimplemented: `ExprKind::InlineAsm` is not further destructured at the moment"
+                );
             },
             ExprKind::OffsetOf(container, ref fields) => {
                 bind!(self, container, fields);
@@ -576,7 +858,7 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
                 bind!(self, qpath, fields);
                 opt_bind!(self, base);
                 kind!("Struct({qpath}, {fields}, {base})");
-                self.qpath(qpath);
+                self.qpath_res(qpath, &self.hir_id(expr, expr.value.hir_id));
                 self.slice(fields, |field| {
                     self.ident(field!(field.ident));
                     self.expr(field!(field.expr));
@@ -644,7 +926,7 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
             PatKind::Struct(ref qpath, fields, ignore) => {
                 bind!(self, qpath, fields);
                 kind!("Struct(ref {qpath}, {fields}, {ignore})");
-                self.qpath(qpath);
+                self.qpath_res(qpath, &self.hir_id(pat, pat.value.hir_id));
                 self.slice(fields, |field| {
                     self.ident(field!(field.ident));
                     self.pat(field!(field.pat));
@@ -658,13 +940,13 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
             PatKind::TupleStruct(ref qpath, fields, skip_pos) => {
                 bind!(self, qpath, fields);
                 kind!("TupleStruct(ref {qpath}, {fields}, {skip_pos:?})");
-                self.qpath(qpath);
+                self.qpath_res(qpath, &self.hir_id(pat, pat.value.hir_id));
                 self.slice(fields, |pat| self.pat(pat));
             },
             PatKind::Path(ref qpath) => {
                 bind!(self, qpath);
                 kind!("Path(ref {qpath})");
-                self.qpath(qpath);
+                self.qpath_res(qpath, &self.hir_id(pat, pat.value.hir_id));
             },
             PatKind::Tuple(fields, skip_pos) => {
                 bind!(self, fields);
@@ -733,9 +1015,13 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
     }
 }
 
-fn has_attr(cx: &LateContext<'_>, hir_id: hir::HirId) -> bool {
+fn author_mode(cx: &LateContext<'_>, hir_id: HirId) -> Option<Mode> {
     let attrs = cx.tcx.hir().attrs(hir_id);
-    get_attr(cx.sess(), attrs, "author").count() > 0
+    let attr = get_attr(cx.sess(), attrs, "author").next()?;
+    match attr.value_str() {
+        Some(name) if name != sym::ignore => Some(Mode::Lint(name)),
+        _ => Some(Mode::Chain),
+    }
 }
 
 fn path_to_string(path: &QPath<'_>) -> String {
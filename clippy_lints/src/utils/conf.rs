@@ -4,6 +4,7 @@
 
 use rustc_session::Session;
 use rustc_span::{BytePos, Pos, SourceFile, Span, SyntaxContext};
+use semver::{Version, VersionReq};
 use serde::de::{Deserializer, IgnoredAny, IntoDeserializer, MapAccess, Visitor};
 use serde::Deserialize;
 use std::fmt::{Debug, Display, Formatter};
@@ -36,6 +37,13 @@ const DEFAULT_DOC_VALID_IDENTS: &[&str] = &[
 ];
 const DEFAULT_DISALLOWED_NAMES: &[&str] = &["foo", "baz", "quux"];
 const DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS: &[&str] = &["i", "j", "x", "y", "z", "w", "n"];
+#[rustfmt::skip]
+const KNOWN_FORMAT_LIKE_MACROS: &[&str] = &[
+    "anyhow::anyhow", "anyhow::bail", "anyhow::ensure",
+    "log::debug", "log::error", "log::info", "log::log", "log::trace", "log::warn",
+    "thiserror::Error",
+    "tracing::debug", "tracing::error", "tracing::event", "tracing::info", "tracing::trace", "tracing::warn",
+];
 
 /// Holds information used by `MISSING_ENFORCED_IMPORT_RENAMES` lint.
 #[derive(Clone, Debug, Deserialize)]
@@ -68,6 +76,103 @@ impl DisallowedPath {
     }
 }
 
+/// An entry of `disallowed-suppressions`, either the name of a lint or lint group, or a lint with
+/// the pattern the reason of its suppressions has to match.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum DisallowedSuppression {
+    Simple(String),
+    WithReason { lint: String, reason: String },
+}
+
+impl DisallowedSuppression {
+    pub fn lint(&self) -> &str {
+        let (Self::Simple(lint) | Self::WithReason { lint, .. }) = self;
+
+        lint
+    }
+}
+
+/// An entry of `allowed-duplicate-crates`, either `name` or `name@requirement`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub struct AllowedDuplicateCrate {
+    pub name: String,
+    pub version: Option<VersionReq>,
+}
+
+impl AllowedDuplicateCrate {
+    pub fn matches(&self, name: &str, version: &Version) -> bool {
+        self.name == name && self.version.as_ref().map_or(true, |req| req.matches(version))
+    }
+}
+
+impl TryFrom<String> for AllowedDuplicateCrate {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.split_once('@') {
+            Some((name, req)) => match VersionReq::parse(req) {
+                Ok(version) => Ok(Self {
+                    name: name.to_string(),
+                    version: Some(version),
+                }),
+                Err(e) => Err(format!("invalid version requirement `{req}`: {e}")),
+            },
+            None => Ok(Self {
+                name: value,
+                version: None,
+            }),
+        }
+    }
+}
+
+/// A lint declared in the `custom-lints` list of the configuration file.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomLint {
+    pub name: String,
+    pub message: String,
+    #[serde(default)]
+    pub level: CustomLintLevel,
+    pub help: Option<String>,
+    pub pattern: CustomLintPattern,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CustomLintLevel {
+    Allow,
+    #[default]
+    Warn,
+    Deny,
+    Forbid,
+}
+
+/// The expressions a [`CustomLint`] is emitted on. Exactly one of `method` and `path` has to be
+/// given.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomLintPattern {
+    /// The name of a called method, e.g. `unwrap`.
+    pub method: Option<String>,
+    /// The path of the type of the method receiver, e.g. `crate::db::Row`.
+    pub receiver: Option<String>,
+    /// The path of a called function or method, e.g. `std::thread::sleep`.
+    pub path: Option<String>,
+    /// The arguments of the call. For `path` patterns matching a method call, the receiver is the
+    /// first argument.
+    pub args: Option<Vec<CustomLintArg>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CustomLintArg {
+    #[serde(rename = "_")]
+    Any,
+    Literal,
+}
+
 /// Conf with parse errors
 #[derive(Default)]
 pub struct TryConf {
@@ -294,7 +399,7 @@ define_Conf! {
     ///
     /// Suppress lints whenever the suggested change would cause breakage for other crates.
     (avoid_breaking_exported_api: bool = true),
-    /// Lint: MANUAL_SPLIT_ONCE, MANUAL_STR_REPEAT, CLONED_INSTEAD_OF_COPIED, REDUNDANT_FIELD_NAMES, OPTION_MAP_UNWRAP_OR, REDUNDANT_STATIC_LIFETIMES, FILTER_MAP_NEXT, CHECKED_CONVERSIONS, MANUAL_RANGE_CONTAINS, USE_SELF, MEM_REPLACE_WITH_DEFAULT, MANUAL_NON_EXHAUSTIVE, OPTION_AS_REF_DEREF, MAP_UNWRAP_OR, MATCH_LIKE_MATCHES_MACRO, MANUAL_STRIP, MISSING_CONST_FOR_FN, UNNESTED_OR_PATTERNS, FROM_OVER_INTO, PTR_AS_PTR, IF_THEN_SOME_ELSE_NONE, APPROX_CONSTANT, DEPRECATED_CFG_ATTR, INDEX_REFUTABLE_SLICE, MAP_CLONE, BORROW_AS_PTR, MANUAL_BITS, ERR_EXPECT, CAST_ABS_TO_UNSIGNED, UNINLINED_FORMAT_ARGS, MANUAL_CLAMP, MANUAL_LET_ELSE, UNCHECKED_DURATION_SUBTRACTION, COLLAPSIBLE_STR_REPLACE, SEEK_FROM_CURRENT, SEEK_REWIND, UNNECESSARY_LAZY_EVALUATIONS, TRANSMUTE_PTR_TO_REF, ALMOST_COMPLETE_RANGE, NEEDLESS_BORROW, DERIVABLE_IMPLS, MANUAL_IS_ASCII_CHECK, MANUAL_REM_EUCLID, MANUAL_RETAIN, TYPE_REPETITION_IN_BOUNDS, TUPLE_ARRAY_CONVERSIONS, MANUAL_TRY_FOLD.
+    /// Lint: MANUAL_SPLIT_ONCE, MANUAL_STR_REPEAT, CLONED_INSTEAD_OF_COPIED, REDUNDANT_FIELD_NAMES, OPTION_MAP_UNWRAP_OR, REDUNDANT_STATIC_LIFETIMES, FILTER_MAP_NEXT, CHECKED_CONVERSIONS, MANUAL_RANGE_CONTAINS, USE_SELF, MEM_REPLACE_WITH_DEFAULT, MANUAL_NON_EXHAUSTIVE, OPTION_AS_REF_DEREF, MAP_UNWRAP_OR, MATCH_LIKE_MATCHES_MACRO, MANUAL_STRIP, MISSING_CONST_FOR_FN, UNNESTED_OR_PATTERNS, FROM_OVER_INTO, PTR_AS_PTR, IF_THEN_SOME_ELSE_NONE, APPROX_CONSTANT, DEPRECATED_CFG_ATTR, INDEX_REFUTABLE_SLICE, MAP_CLONE, BORROW_AS_PTR, MANUAL_BITS, ERR_EXPECT, CAST_ABS_TO_UNSIGNED, UNINLINED_FORMAT_ARGS, MANUAL_CLAMP, MANUAL_LET_ELSE, UNCHECKED_DURATION_SUBTRACTION, COLLAPSIBLE_STR_REPLACE, SEEK_FROM_CURRENT, SEEK_REWIND, UNNECESSARY_LAZY_EVALUATIONS, TRANSMUTE_PTR_TO_REF, ALMOST_COMPLETE_RANGE, NEEDLESS_BORROW, DERIVABLE_IMPLS, MANUAL_IS_ASCII_CHECK, MANUAL_REM_EUCLID, MANUAL_RETAIN, TYPE_REPETITION_IN_BOUNDS, TUPLE_ARRAY_CONVERSIONS, MANUAL_TRY_FOLD, INCOMPATIBLE_MSRV.
     ///
     /// The minimum rust version that the project supports
     (msrv: Option<String> = None),
@@ -392,6 +497,11 @@ define_Conf! {
     ///
     /// The maximum allowed stack size for functions in bytes
     (stack_size_threshold: u64 = 512_000),
+    /// Lint: LARGE_STACK_FRAMES.
+    ///
+    /// Whether to add the stack space used by the functions of the crate called from a function,
+    /// through direct calls, to estimate its worst-case stack usage
+    (stack_size_include_callees: bool = false),
     /// Lint: VEC_BOX.
     ///
     /// The size of the boxed type in bytes, where boxing in a `Vec` is allowed
@@ -424,6 +534,20 @@ define_Conf! {
     ///
     /// The list of disallowed types, written as fully qualified paths.
     (disallowed_types: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    /// Lint: DISALLOWED_SUPPRESSIONS.
+    ///
+    /// The lints and lint groups that can't be allowed or expected with attributes. Entries with a
+    /// `reason` can be suppressed if the attribute has a `reason` matching this regex.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// disallowed-suppressions = [
+    ///     "clippy::unwrap_used",
+    ///     { lint = "clippy::indexing_slicing", reason = "^TICKET-[0-9]+" },
+    /// ]
+    /// ```
+    (disallowed_suppressions: Vec<crate::utils::conf::DisallowedSuppression> = Vec::new()),
     /// Lint: UNREADABLE_LITERAL.
     ///
     /// Should the fraction of a decimal be linted to include separators.
@@ -442,6 +566,12 @@ define_Conf! {
     ///
     /// For internal testing only, ignores the current `publish` settings in the Cargo manifest.
     (cargo_ignore_publish: bool = false),
+    /// Lint: MULTIPLE_CRATE_VERSIONS.
+    ///
+    /// The crates allowed to be used in multiple versions. An entry is either the name of a crate, which
+    /// allows all of its versions, or `name@requirement` to only allow the versions matching a version
+    /// requirement, e.g. `"windows-sys@0.45"`.
+    (allowed_duplicate_crates: Vec<crate::utils::conf::AllowedDuplicateCrate> = Vec::new()),
     /// Lint: NONSTANDARD_MACRO_BRACES.
     ///
     /// Enforce the named macros always use the braces specified.
@@ -581,6 +711,56 @@ define_Conf! {
     /// for _ in &mut *rmvec {}
     /// ```
     (enforce_iter_loop_reborrow: bool = false),
+    /// Lint: none.
+    ///
+    /// Additional lints specific to the project. Each lint is emitted on the calls matching its
+    /// `pattern` and can be allowed or denied like any other lint, e.g. `#[allow(clippy::row_unwrap)]`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// [[custom-lints]]
+    /// name = "row_unwrap"
+    /// message = "called `unwrap` on a database row"
+    /// level = "deny"
+    /// help = "use `Row::try_get` instead"
+    /// pattern = { method = "unwrap", receiver = "crate::db::Row" }
+    ///
+    /// [[custom-lints]]
+    /// name = "hardcoded_sleep"
+    /// message = "sleeping for a hardcoded duration"
+    /// pattern = { path = "std::time::Duration::from_secs", args = ["literal"] }
+    /// ```
+    (custom_lints: Vec<crate::utils::conf::CustomLint> = Vec::new()),
+    /// Lint: none.
+    ///
+    /// Paths of lint plugins to load, relative to the directory containing the configuration file.
+    /// Plugins listed in the `CLIPPY_PLUGINS` environment variable are loaded as well.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// plugins = ["target/release/libproject_lints.so"]
+    /// ```
+    (plugins: Vec<std::path::PathBuf> = Vec::new()),
+    /// Lint: INVALID_REGEX, TRIVIAL_REGEX, REGEX_CREATION_IN_LOOPS.
+    ///
+    /// Paths of functions compiling the regex passed as their first argument, which are checked
+    /// like `regex::Regex::new`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// regex-constructors = ["my_crate::util::cached_regex"]
+    /// ```
+    (regex_constructors: Vec<String> = Vec::new()),
+    /// Lint: FORMAT_IN_FORMAT_ARGS, TO_STRING_IN_FORMAT_ARGS, UNINLINED_FORMAT_ARGS, UNUSED_FORMAT_SPECS.
+    ///
+    /// Paths of macros, in addition to the `std` ones, which take a format string and its arguments,
+    /// like the logging macros of `log` and `tracing`. Derive macros are accepted as well, for
+    /// `#[error("..")]` attributes of `thiserror::Error`. The value `".."` can be used as part of
+    /// the list to also check the format-like macros of `anyhow`, `log`, `thiserror` and `tracing`.
+    (format_like_macros: Vec<String> = Vec::new()),
 }
 
 /// Search for the configuration file.
@@ -648,6 +828,7 @@ pub fn read(sess: &Session, path: &Path) -> TryConf {
         Ok(mut conf) => {
             extend_vec_if_indicator_present(&mut conf.conf.doc_valid_idents, DEFAULT_DOC_VALID_IDENTS);
             extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
+            extend_vec_if_indicator_present(&mut conf.conf.format_like_macros, KNOWN_FORMAT_LIKE_MACROS);
             // TODO: THIS SHOULD BE TESTED, this comment will be gone soon
             if conf.conf.allowed_idents_below_min_chars.contains(&"..".to_owned()) {
                 conf.conf
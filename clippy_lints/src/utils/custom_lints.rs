@@ -0,0 +1,256 @@
+//! Lints declared by the user in the `custom-lints` list of the configuration file.
+//!
+//! Every entry is registered as a `clippy::<name>` lint, so it can be allowed, denied and expected
+//! like Clippy's own lints. The paths used in the patterns are resolved once per crate, entries
+//! referring to items which can't be found in the crate graph are ignored.
+
+use crate::renamed_lints::RENAMED_LINTS;
+use crate::utils::conf::{CustomLint, CustomLintArg, CustomLintLevel, CustomLintPattern};
+use clippy_utils::def_path_def_ids;
+use clippy_utils::diagnostics::is_lint_filtered;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::{Expr, ExprKind, UnOp};
+use rustc_lint::{CheckLintNameResult, LateContext, LateLintPass, Level, Lint, LintContext, LintPass, LintStore};
+use rustc_middle::ty;
+use rustc_session::Session;
+use rustc_span::symbol::{sym, Ident, Symbol};
+
+/// Registers the lints declared in the configuration along with the pass emitting them. Invalid
+/// declarations are reported as errors and skipped.
+pub fn register(store: &mut LintStore, sess: &Session, custom_lints: &[CustomLint]) {
+    let tools = std::iter::once(Ident::with_dummy_span(sym::clippy)).collect();
+    let mut names = FxHashSet::default();
+    let mut lints = Vec::new();
+    for custom_lint in custom_lints {
+        let name = &custom_lint.name;
+        let error = if name.is_empty()
+            || !name.starts_with(|c: char| c.is_ascii_lowercase())
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            Some("the name must be a lowercase `snake_case` identifier".to_string())
+        } else if !names.insert(name.as_str()) {
+            Some("a custom lint with the same name is already declared".to_string())
+        } else if !matches!(
+            store.check_lint_name(name, Some(sym::clippy), &tools),
+            CheckLintNameResult::NoLint(_)
+        ) || RENAMED_LINTS
+            .iter()
+            .any(|(old, _)| old.strip_prefix("clippy::") == Some(name))
+        {
+            Some(format!("`clippy::{name}` is already a Clippy lint"))
+        } else {
+            check_pattern(&custom_lint.pattern).err()
+        };
+        if let Some(error) = error {
+            sess.err(format!(
+                "error reading Clippy's configuration file: invalid custom lint `{name}`: {error}"
+            ));
+            continue;
+        }
+
+        let lint: &'static Lint = Box::leak(Box::new(Lint {
+            name: Box::leak(format!("clippy::{name}").into_boxed_str()),
+            default_level: match custom_lint.level {
+                CustomLintLevel::Allow => Level::Allow,
+                CustomLintLevel::Warn => Level::Warn,
+                CustomLintLevel::Deny => Level::Deny,
+                CustomLintLevel::Forbid => Level::Forbid,
+            },
+            desc: Box::leak(custom_lint.message.clone().into_boxed_str()),
+            ..Lint::default_fields_for_macro()
+        }));
+        lints.push((lint, custom_lint.clone()));
+    }
+
+    if lints.is_empty() {
+        return;
+    }
+    store.register_lints(&lints.iter().map(|&(lint, _)| lint).collect::<Vec<_>>());
+    store.register_late_pass(move |_| Box::new(CustomLints::new(lints.clone())));
+}
+
+fn check_pattern(pattern: &CustomLintPattern) -> Result<(), String> {
+    match pattern {
+        CustomLintPattern {
+            method: Some(_),
+            path: Some(_),
+            ..
+        }
+        | CustomLintPattern {
+            method: None,
+            path: None,
+            ..
+        } => Err("the pattern must contain exactly one of `method` and `path`".to_string()),
+        CustomLintPattern {
+            path: Some(_),
+            receiver: Some(_),
+            ..
+        } => Err("`receiver` can only be used with `method` patterns".to_string()),
+        _ => Ok(()),
+    }
+}
+
+pub struct CustomLints {
+    lints: Vec<(&'static Lint, CustomLint)>,
+    /// The lints with their paths resolved in the current crate.
+    matchers: Vec<Matcher>,
+}
+
+struct Matcher {
+    lint: &'static Lint,
+    message: String,
+    help: Option<String>,
+    callee: Callee,
+    args: Option<Vec<CustomLintArg>>,
+}
+
+enum Callee {
+    Method {
+        name: Symbol,
+        /// The possible receiver types, or `None` if any receiver is accepted.
+        receiver: Option<FxHashSet<DefId>>,
+    },
+    Path(FxHashSet<DefId>),
+}
+
+impl CustomLints {
+    pub fn new(lints: Vec<(&'static Lint, CustomLint)>) -> Self {
+        Self {
+            lints,
+            matchers: Vec::new(),
+        }
+    }
+}
+
+// The lints are only known at runtime, so `impl_lint_pass!` can't be used.
+#[allow(rustc::lint_pass_impl_without_macro)]
+impl LintPass for CustomLints {
+    fn name(&self) -> &'static str {
+        "CustomLints"
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for CustomLints {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.matchers = self
+            .lints
+            .iter()
+            .filter_map(|(lint, custom_lint)| {
+                let pattern = &custom_lint.pattern;
+                let callee = if let Some(path) = &pattern.path {
+                    let def_ids = resolve(cx, path);
+                    if def_ids.is_empty() {
+                        return None;
+                    }
+                    Callee::Path(def_ids)
+                } else {
+                    let receiver = match &pattern.receiver {
+                        Some(path) => {
+                            let def_ids = resolve(cx, path);
+                            if def_ids.is_empty() {
+                                return None;
+                            }
+                            Some(def_ids)
+                        },
+                        None => None,
+                    };
+                    Callee::Method {
+                        name: Symbol::intern(pattern.method.as_deref()?),
+                        receiver,
+                    }
+                };
+                Some(Matcher {
+                    lint,
+                    message: custom_lint.message.clone(),
+                    help: custom_lint.help.clone(),
+                    callee,
+                    args: pattern.args.clone(),
+                })
+            })
+            .collect();
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if self.matchers.is_empty() || expr.span.from_expansion() {
+            return;
+        }
+        for matcher in &self.matchers {
+            if matcher.matches(cx, expr) && !is_lint_filtered(cx, matcher.lint, expr.span) {
+                // Not emitted with `span_lint_and_help`, which links to the documentation of Clippy's lints
+                cx.struct_span_lint(matcher.lint, expr.span, matcher.message.clone(), |diag| {
+                    if let Some(help) = &matcher.help {
+                        diag.help(help.clone());
+                    }
+                    diag
+                });
+            }
+        }
+    }
+}
+
+impl Matcher {
+    fn matches(&self, cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+        match (&self.callee, expr.kind) {
+            (Callee::Method { name, receiver }, ExprKind::MethodCall(path, recv, args, _)) => {
+                path.ident.name == *name
+                    && receiver.as_ref().map_or(true, |receiver| {
+                        matches!(
+                            cx.typeck_results().expr_ty(recv).peel_refs().kind(),
+                            ty::Adt(adt, _) if receiver.contains(&adt.did())
+                        )
+                    })
+                    && self.args_match(None, args)
+            },
+            (Callee::Path(def_ids), ExprKind::MethodCall(_, recv, args, _)) => {
+                cx.typeck_results()
+                    .type_dependent_def_id(expr.hir_id)
+                    .map_or(false, |def_id| def_ids.contains(&def_id))
+                    && self.args_match(Some(recv), args)
+            },
+            (Callee::Path(def_ids), ExprKind::Call(func, args)) => {
+                let res = match func.kind {
+                    ExprKind::Path(ref qpath) => cx.qpath_res(qpath, func.hir_id),
+                    _ => return false,
+                };
+                res.opt_def_id().map_or(false, |def_id| def_ids.contains(&def_id)) && self.args_match(None, args)
+            },
+            _ => false,
+        }
+    }
+
+    fn args_match(&self, receiver: Option<&Expr<'_>>, args: &[Expr<'_>]) -> bool {
+        let Some(patterns) = &self.args else {
+            return true;
+        };
+        usize::from(receiver.is_some()) + args.len() == patterns.len()
+            && receiver
+                .into_iter()
+                .chain(args)
+                .zip(patterns)
+                .all(|(arg, pattern)| match pattern {
+                    CustomLintArg::Any => true,
+                    CustomLintArg::Literal => is_literal(arg),
+                })
+    }
+}
+
+fn is_literal(expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Lit(_) => true,
+        ExprKind::Unary(UnOp::Neg, inner) => matches!(inner.kind, ExprKind::Lit(_)),
+        _ => false,
+    }
+}
+
+/// Resolves `path`, where a leading `crate` segment refers to the crate being linted.
+fn resolve(cx: &LateContext<'_>, path: &str) -> FxHashSet<DefId> {
+    let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+    let mut segments: Vec<&str> = path.split("::").collect();
+    if segments[0] == "crate" {
+        segments[0] = crate_name.as_str();
+    }
+    def_path_def_ids(cx, &segments).collect()
+}
@@ -1,6 +1,6 @@
 use clippy_utils::diagnostics::span_lint_and_help;
 use clippy_utils::ty::match_type;
-use clippy_utils::{is_lint_allowed, paths};
+use clippy_utils::{paths, should_skip_lint};
 use if_chain::if_chain;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::{Expr, ExprKind};
@@ -52,7 +52,7 @@ impl CompilerLintFunctions {
 
 impl<'tcx> LateLintPass<'tcx> for CompilerLintFunctions {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
-        if is_lint_allowed(cx, COMPILER_LINT_FUNCTIONS, expr.hir_id) {
+        if should_skip_lint(cx, COMPILER_LINT_FUNCTIONS, expr.hir_id) {
             return;
         }
 
@@ -41,7 +41,14 @@ const JSON_OUTPUT_FILE: &str = "../util/gh-pages/lints.json";
 /// This is the markdown output file of the lint collector.
 const MARKDOWN_OUTPUT_FILE: &str = "../book/src/lint_configuration.md";
 /// These lints are excluded from the export.
-const BLACK_LISTED_LINTS: &[&str] = &["lint_author", "dump_hir", "internal_metadata_collector"];
+const BLACK_LISTED_LINTS: &[&str] = &[
+    "lint_author",
+    "dump_hir",
+    "internal_metadata_collector",
+    "suppressions",
+    "type_sizes",
+    "unsafe_inventory",
+];
 /// These groups will be ignored by the lint group matcher. This is useful for collections like
 /// `clippy::all`
 const IGNORED_LINT_GROUPS: [&str; 1] = ["clippy::all"];
@@ -187,7 +194,6 @@ This lint has the following configuration variables:
         self.config
             .iter()
             .filter(|config| config.deprecation_reason.is_none())
-            .filter(|config| !config.lints.is_empty())
             .map(map_fn)
             .join("\n")
     }
@@ -1,9 +1,14 @@
 pub mod author;
 pub mod conf;
+pub mod custom_lints;
 pub mod dump_hir;
 pub mod format_args_collector;
 #[cfg(feature = "internal")]
 pub mod internal_lints;
+pub mod reports;
+pub mod suppressions;
+pub mod type_sizes;
+pub mod unsafe_inventory;
 #[cfg(feature = "internal")]
 use itertools::Itertools;
 
@@ -53,8 +58,22 @@ impl ClippyConfiguration {
 
     #[cfg(feature = "internal")]
     fn to_markdown_paragraph(&self) -> String {
+        let affected_lints = if self.lints.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\n---\n**Affected lints:**\n{}\n",
+                self.lints
+                    .iter()
+                    .map(|name| name.to_string().split_whitespace().next().unwrap().to_string())
+                    .map(|name| format!(
+                        "* [`{name}`](https://rust-lang.github.io/rust-clippy/master/index.html#{name})"
+                    ))
+                    .join("\n"),
+            )
+        };
         format!(
-            "## `{}`\n{}\n\n**Default Value:** `{}` (`{}`)\n\n---\n**Affected lints:**\n{}\n\n",
+            "## `{}`\n{}\n\n**Default Value:** `{}` (`{}`)\n{affected_lints}\n",
             self.name,
             self.doc
                 .lines()
@@ -62,11 +81,6 @@ impl ClippyConfiguration {
                 .join("\n"),
             self.default,
             self.config_type,
-            self.lints
-                .iter()
-                .map(|name| name.to_string().split_whitespace().next().unwrap().to_string())
-                .map(|name| format!("* [`{name}`](https://rust-lang.github.io/rust-clippy/master/index.html#{name})"))
-                .join("\n"),
         )
     }
     #[cfg(feature = "internal")]
@@ -90,6 +104,8 @@ fn collect_configs() -> Vec<ClippyConfiguration> {
 /// ```rust, ignore
 /// Some(["lint_name_1", "lint_name_2"], "Papa penguin, papa penguin")
 /// ```
+///
+/// Options which don't configure a specific lint use `Lint: none.`, which yields an empty list.
 fn parse_config_field_doc(doc_comment: &str) -> Option<(Vec<String>, String)> {
     const DOC_START: &str = " Lint: ";
     if_chain! {
@@ -104,6 +120,7 @@ fn parse_config_field_doc(doc_comment: &str) -> Option<(Vec<String>, String)> {
             let lints: Vec<String> = doc_comment
                 .split_off(DOC_START.len())
                 .split(", ")
+                .filter(|lint| *lint != "none")
                 .map(str::to_string)
                 .collect();
 
@@ -0,0 +1,74 @@
+//! Helpers shared by the reports written when `CLIPPY_UNSAFE_INVENTORY`, `CLIPPY_TYPE_SIZES` or
+//! `CLIPPY_SUPPRESSIONS` is set.
+//!
+//! The value of the environment variable is the directory the reports are written to, one
+//! `<crate name><extra filename>` file per compiled crate. If the value is `-`, the reports are
+//! printed to stdout instead.
+
+use rustc_lint::{LateContext, LintContext};
+use rustc_span::def_id::LOCAL_CRATE;
+use rustc_span::Span;
+use serde::Serialize;
+use std::path::Path;
+use std::{fmt, fs};
+
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    file: String,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl Location {
+    pub fn from_span(cx: &LateContext<'_>, span: Span) -> Self {
+        let source_map = cx.sess().source_map();
+        let lo = source_map.lookup_char_pos(span.lo());
+        let hi = source_map.lookup_char_pos(span.hi());
+
+        Self {
+            file: format!("{}", lo.file.name.prefer_remapped()),
+            line: lo.line,
+            column: lo.col_display + 1,
+            end_line: hi.line,
+            end_column: hi.col_display + 1,
+        }
+    }
+}
+
+/// Displays the start of the location as `file:line:column`.
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// Writes `report` as JSON to `<output>/<crate name><extra filename>.json`. `name` describes the
+/// report in the warning emitted if it can't be written.
+pub fn write_report(cx: &LateContext<'_>, output: &str, name: &str, report: &impl Serialize) {
+    let json = serde_json::to_string_pretty(report).unwrap();
+    write(cx, output, name, "json", &(json + "\n"));
+}
+
+/// Writes the text `report` to `<output>/<crate name><extra filename>.txt`.
+pub fn write_text_report(cx: &LateContext<'_>, output: &str, name: &str, report: &str) {
+    write(cx, output, name, "txt", report);
+}
+
+fn write(cx: &LateContext<'_>, output: &str, name: &str, extension: &str, report: &str) {
+    if output == "-" {
+        print!("{report}");
+        return;
+    }
+
+    let path = Path::new(output).join(format!(
+        "{}{}.{extension}",
+        cx.tcx.crate_name(LOCAL_CRATE),
+        cx.sess().opts.cg.extra_filename
+    ));
+    if let Err(e) = fs::create_dir_all(output).and_then(|()| fs::write(&path, report)) {
+        cx.sess()
+            .warn(format!("error writing the {name} to `{}`: {e}", path.display()));
+    }
+}
@@ -0,0 +1,75 @@
+//! Collects every suppression of a lint in a crate and writes them out as JSON.
+//!
+//! This pass is only registered if the `CLIPPY_SUPPRESSIONS` environment variable is set. Its value
+//! is the directory the report is written to, one `<crate name><extra filename>.json` file per
+//! compiled crate, see the `reports` module.
+//!
+//! Every lint or lint group named in an `#[allow]` or `#[expect]` attribute is recorded along with
+//! its location and the `reason` of the attribute.
+
+use crate::disallowed_suppressions::Suppression;
+use crate::utils::reports::{write_report, Location};
+use rustc_ast::Attribute;
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use serde::Serialize;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Writes a JSON report of all `#[allow]` and `#[expect]` attributes of the crate when the
+    /// `CLIPPY_SUPPRESSIONS` environment variable is set. This never emits
+    /// any diagnostics.
+    pub SUPPRESSIONS,
+    internal_warn,
+    "helper to report the suppressions of lints"
+}
+
+impl_lint_pass!(Suppressions => [SUPPRESSIONS]);
+
+pub struct Suppressions {
+    output: String,
+    entries: Vec<SuppressionEntry>,
+}
+
+impl Suppressions {
+    pub fn new(output: String) -> Self {
+        Self {
+            output,
+            entries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SuppressionEntry {
+    lint: String,
+    level: String,
+    reason: Option<String>,
+    location: Location,
+}
+
+impl<'tcx> LateLintPass<'tcx> for Suppressions {
+    fn check_attribute(&mut self, cx: &LateContext<'tcx>, attr: &'tcx Attribute) {
+        if in_external_macro(cx.sess(), attr.span) {
+            return;
+        }
+        let Some(suppression) = Suppression::from_attr(attr) else {
+            return;
+        };
+        for (lint, span) in suppression.lints {
+            self.entries.push(SuppressionEntry {
+                lint,
+                level: suppression.level.to_string(),
+                reason: suppression.reason.map(|reason| reason.to_string()),
+                location: Location::from_span(cx, span),
+            });
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.sort_by(|a, b| a.location.cmp(&b.location));
+        write_report(cx, &self.output, "suppression report", &entries);
+    }
+}
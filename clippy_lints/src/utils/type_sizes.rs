@@ -0,0 +1,145 @@
+//! Reports the size of every type defined in a crate.
+//!
+//! This pass is only registered if the `CLIPPY_TYPE_SIZES` environment variable is set. Its value
+//! is the directory the report is written to, one `<crate name><extra filename>.txt` file per
+//! compiled crate, see the `reports` module.
+//!
+//! Every struct, enum and union whose layout doesn't depend on generic parameters is listed with
+//! its size, alignment and niche, largest first. Enums also list the size of their variants (as
+//! computed for `large_enum_variant`), and are flagged when the difference between the two largest
+//! variants exceeds `enum-variant-size-threshold`.
+
+use crate::utils::reports::{write_text_report, Location};
+use clippy_utils::ty::AdtVariantInfo;
+use rustc_hir::{Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use std::fmt::Write as _;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Writes a report of the size of all types of the crate when the
+    /// `CLIPPY_TYPE_SIZES` environment variable is set. This never emits
+    /// any diagnostics.
+    pub TYPE_SIZES,
+    internal_warn,
+    "helper to report the size of the types of a crate"
+}
+
+impl_lint_pass!(TypeSizes => [TYPE_SIZES]);
+
+pub struct TypeSizes {
+    output: String,
+    enum_variant_size_threshold: u64,
+    entries: Vec<TypeSize>,
+}
+
+impl TypeSizes {
+    pub fn new(output: String, enum_variant_size_threshold: u64) -> Self {
+        Self {
+            output,
+            enum_variant_size_threshold,
+            entries: Vec::new(),
+        }
+    }
+}
+
+struct TypeSize {
+    name: String,
+    location: Location,
+    size: u64,
+    align: u64,
+    niche_values: Option<u128>,
+    /// The name and size of each variant of an enum, largest first.
+    variants: Vec<(String, u64)>,
+    exceeds_threshold: bool,
+}
+
+impl TypeSize {
+    fn write(&self, report: &mut String) {
+        let niche = match self.niche_values {
+            Some(1) => "niche with 1 available value".to_string(),
+            Some(values) => format!("niche with {values} available values"),
+            None => "no niche".to_string(),
+        };
+        let _ = write!(
+            report,
+            "`{}` ({}): {}, alignment {}, {niche}",
+            self.name,
+            self.location,
+            bytes(self.size),
+            self.align
+        );
+        if self.exceeds_threshold {
+            report.push_str(", variant size difference exceeds `enum-variant-size-threshold`");
+        }
+        report.push('\n');
+        for (name, size) in &self.variants {
+            let _ = writeln!(report, "    variant `{name}`: {}", bytes(*size));
+        }
+    }
+}
+
+fn bytes(size: u64) -> String {
+    if size == 1 {
+        "1 byte".to_string()
+    } else {
+        format!("{size} bytes")
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for TypeSizes {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if !matches!(
+            item.kind,
+            ItemKind::Struct(..) | ItemKind::Enum(..) | ItemKind::Union(..)
+        ) || in_external_macro(cx.sess(), item.span)
+        {
+            return;
+        }
+        let ty = cx.tcx.type_of(item.owner_id).instantiate_identity();
+        let ty::Adt(adt, args) = *ty.kind() else {
+            return;
+        };
+        // The layout of generic types depends on their parameters
+        let Ok(layout) = cx.tcx.layout_of(cx.tcx.param_env(item.owner_id).and(ty)) else {
+            return;
+        };
+
+        let mut variants = Vec::new();
+        let mut exceeds_threshold = false;
+        if adt.is_enum() {
+            let variants_size = AdtVariantInfo::new(cx, adt, args);
+            exceeds_threshold = variants_size.len() > 1
+                && variants_size[0].size - variants_size[1].size > self.enum_variant_size_threshold;
+            variants = variants_size
+                .iter()
+                .map(|info| (adt.variant(info.ind.into()).name.to_string(), info.size))
+                .collect();
+        }
+
+        self.entries.push(TypeSize {
+            name: cx.tcx.def_path_str(item.owner_id),
+            location: Location::from_span(cx, item.span),
+            size: layout.size.bytes(),
+            align: layout.align.abi.bytes(),
+            niche_values: layout.largest_niche.map(|niche| niche.available(&cx.tcx)),
+            variants,
+            exceeds_threshold,
+        });
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+        let crate_name = cx.tcx.crate_name(rustc_span::def_id::LOCAL_CRATE);
+        let mut report = format!("type sizes of crate `{crate_name}`, largest first:\n");
+        for entry in &entries {
+            entry.write(&mut report);
+        }
+
+        write_text_report(cx, &self.output, "type size report", &report);
+    }
+}
@@ -0,0 +1,223 @@
+//! Collects an inventory of every use of `unsafe` in a crate and writes it out as JSON.
+//!
+//! This pass is only registered if the `CLIPPY_UNSAFE_INVENTORY` environment variable is set. Its
+//! value is the directory the inventory is written to, one `<crate name><extra filename>.json` file
+//! per compiled crate, see the `reports` module.
+//!
+//! Every `unsafe` block, `unsafe fn`, `unsafe impl`, `#[no_mangle]` item, `extern` block and call
+//! to `transmute` is recorded along with its location, the `// SAFETY:` comment attached to it (as
+//! found by `undocumented_unsafe_blocks`) and the unsafe operations it contains (as found by
+//! `multiple_unsafe_ops_per_block`).
+
+use crate::multiple_unsafe_ops_per_block::collect_unsafe_exprs;
+use crate::undocumented_unsafe_blocks::{assoc_item_safety_comment, block_safety_comment, item_safety_comment};
+use crate::utils::reports::{write_report, Location};
+use rustc_hir as hir;
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{BlockCheckMode, ExprKind, Node, QPath, UnsafeSource, Unsafety};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span};
+use serde::Serialize;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Writes a JSON inventory of all uses of `unsafe` in the crate when the
+    /// `CLIPPY_UNSAFE_INVENTORY` environment variable is set. This never emits
+    /// any diagnostics.
+    pub UNSAFE_INVENTORY,
+    internal_warn,
+    "helper to collect an inventory of `unsafe` usage"
+}
+
+impl_lint_pass!(UnsafeInventory => [UNSAFE_INVENTORY]);
+
+pub struct UnsafeInventory {
+    output: String,
+    entries: Vec<UnsafeEntry>,
+}
+
+impl UnsafeInventory {
+    pub fn new(output: String) -> Self {
+        Self {
+            output,
+            entries: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, cx: &LateContext<'_>, kind: UnsafeKind, span: Span, safety_comment: Option<String>) {
+        self.push_with_operations(cx, kind, span, safety_comment, Vec::new());
+    }
+
+    fn push_with_operations(
+        &mut self,
+        cx: &LateContext<'_>,
+        kind: UnsafeKind,
+        span: Span,
+        safety_comment: Option<String>,
+        operations: Vec<(&'static str, Span)>,
+    ) {
+        self.entries.push(UnsafeEntry {
+            kind,
+            location: Location::from_span(cx, span),
+            safety_comment,
+            operations: operations
+                .into_iter()
+                .map(|(description, span)| UnsafeOperation {
+                    description,
+                    location: Location::from_span(cx, span),
+                })
+                .collect(),
+        });
+    }
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum UnsafeKind {
+    UnsafeBlock,
+    UnsafeFn,
+    UnsafeImpl,
+    NoMangle,
+    ExternBlock,
+    Transmute,
+}
+
+#[derive(Serialize)]
+struct UnsafeEntry {
+    kind: UnsafeKind,
+    location: Location,
+    safety_comment: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    operations: Vec<UnsafeOperation>,
+}
+
+#[derive(Serialize)]
+struct UnsafeOperation {
+    description: &'static str,
+    location: Location,
+}
+
+impl<'tcx> LateLintPass<'tcx> for UnsafeInventory {
+    fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx hir::Block<'_>) {
+        if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
+            && !in_external_macro(cx.sess(), block.span)
+        {
+            let mut operations = Vec::new();
+            collect_unsafe_exprs(cx, block, &mut operations);
+            self.push_with_operations(
+                cx,
+                UnsafeKind::UnsafeBlock,
+                block.span,
+                block_safety_comment(cx, block.span),
+                operations,
+            );
+        }
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx hir::FnDecl<'_>,
+        body: &'tcx hir::Body<'_>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        let unsafety = match kind {
+            FnKind::ItemFn(_, _, header) => header.unsafety,
+            FnKind::Method(_, sig) => sig.header.unsafety,
+            FnKind::Closure => return,
+        };
+        if unsafety == Unsafety::Unsafe && !in_external_macro(cx.sess(), span) {
+            let safety_comment = match cx.tcx.hir().get_by_def_id(def_id) {
+                Node::Item(item) => item_safety_comment(cx, item),
+                Node::ImplItem(item) => assoc_safety_comment(cx, item.owner_id, item.span),
+                Node::TraitItem(item) => assoc_safety_comment(cx, item.owner_id, item.span),
+                _ => None,
+            };
+            let mut operations = Vec::new();
+            collect_unsafe_exprs(cx, body.value, &mut operations);
+            self.push_with_operations(cx, UnsafeKind::UnsafeFn, span, safety_comment, operations);
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'_>) {
+        if in_external_macro(cx.sess(), item.span) {
+            return;
+        }
+
+        match item.kind {
+            hir::ItemKind::Impl(impl_) if impl_.unsafety == Unsafety::Unsafe => {
+                self.push(cx, UnsafeKind::UnsafeImpl, item.span, item_safety_comment(cx, item));
+            },
+            hir::ItemKind::ForeignMod { .. } => {
+                self.push(cx, UnsafeKind::ExternBlock, item.span, item_safety_comment(cx, item));
+            },
+            _ => {},
+        }
+
+        if has_no_mangle(cx, item.hir_id()) {
+            self.push(cx, UnsafeKind::NoMangle, item.span, item_safety_comment(cx, item));
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'_>) {
+        // Trait methods with a body are checked by `check_fn`
+        if let hir::TraitItemKind::Fn(sig, hir::TraitFn::Required(_)) = item.kind
+            && sig.header.unsafety == Unsafety::Unsafe
+            && !in_external_macro(cx.sess(), item.span)
+        {
+            let safety_comment = assoc_safety_comment(cx, item.owner_id, item.span);
+            self.push(cx, UnsafeKind::UnsafeFn, item.span, safety_comment);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ImplItem<'_>) {
+        if !in_external_macro(cx.sess(), item.span) && has_no_mangle(cx, item.hir_id()) {
+            // The safety comment is above the `#[no_mangle]` attribute
+            let span = cx
+                .tcx
+                .hir()
+                .attrs(item.hir_id())
+                .iter()
+                .filter(|attr| attr.span.ctxt() == item.span.ctxt() && attr.span.lo() < item.span.lo())
+                .fold(item.span, |span, attr| span.to(attr.span));
+            let safety_comment = assoc_safety_comment(cx, item.owner_id, span);
+            self.push(cx, UnsafeKind::NoMangle, item.span, safety_comment);
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
+        if let ExprKind::Call(path_expr, _) = expr.kind
+            && let ExprKind::Path(QPath::Resolved(None, path)) = path_expr.kind
+            && let Some(def_id) = path.res.opt_def_id()
+            && cx.tcx.is_diagnostic_item(sym::transmute, def_id)
+            && !in_external_macro(cx.sess(), expr.span)
+        {
+            self.push(cx, UnsafeKind::Transmute, expr.span, None);
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.sort_by(|a, b| a.location.cmp(&b.location));
+        write_report(cx, &self.output, "unsafe inventory", &entries);
+    }
+}
+
+/// Returns the safety comment of an item of an impl or a trait.
+fn assoc_safety_comment(cx: &LateContext<'_>, owner_id: hir::OwnerId, span: Span) -> Option<String> {
+    let parent = cx.tcx.hir().get_parent_item(owner_id.into());
+    assoc_item_safety_comment(cx, span, cx.tcx.hir().expect_item(parent.def_id).span)
+}
+
+fn has_no_mangle(cx: &LateContext<'_>, hir_id: hir::HirId) -> bool {
+    cx.tcx
+        .hir()
+        .attrs(hir_id)
+        .iter()
+        .any(|attr| attr.has_name(sym::no_mangle))
+}
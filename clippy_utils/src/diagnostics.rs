@@ -8,11 +8,129 @@
 //! Thank you!
 //! ~The `INTERNAL_METADATA_COLLECTOR` lint
 
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{Applicability, Diagnostic, MultiSpan};
 use rustc_hir::HirId;
-use rustc_lint::{LateContext, Lint, LintContext};
+use rustc_lint::{EarlyContext, LateContext, Level, Lint, LintContext};
+use rustc_middle::lint::{LevelAndSource, LintLevelSource};
+use rustc_session::Session;
 use rustc_span::source_map::Span;
-use std::env;
+use rustc_span::{FileName, RealFileName};
+use std::cell::RefCell;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::{env, fs, io};
+
+/// The lines of each file that changed, set with `--only-changed`. A file without ranges changed
+/// entirely.
+static CHANGED_LINES: OnceLock<FxHashMap<PathBuf, Vec<RangeInclusive<usize>>>> = OnceLock::new();
+
+/// Only emits the lints whose primary span is on the lines listed in the file at `path`, which
+/// contains a `path:start-end` range or a `path` per line.
+pub fn emit_only_on_changed_lines(path: &Path) -> io::Result<()> {
+    let mut changed: FxHashMap<PathBuf, Vec<RangeInclusive<usize>>> = FxHashMap::default();
+    for line in fs::read_to_string(path)?.lines().filter(|line| !line.is_empty()) {
+        let (file, range) = match line.rsplit_once(':').and_then(|(file, range)| {
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            Some((file, start.parse().ok()?..=end.parse().ok()?))
+        }) {
+            Some((file, range)) => (file, Some(range)),
+            None => (line, None),
+        };
+        // Deleted files have no lints
+        if let Ok(file) = fs::canonicalize(file) {
+            changed.entry(file).or_default().extend(range);
+        }
+    }
+    let _ = CHANGED_LINES.set(changed);
+    Ok(())
+}
+
+thread_local! {
+    /// The spans of the lints named in `#[allow]` and `#[expect]` attributes that suppressed a
+    /// lint.
+    static USED_SUPPRESSIONS: RefCell<FxHashSet<Span>> = RefCell::default();
+}
+
+/// Records the `#[allow]` or `#[expect]` attribute a lint level comes from as used. This is done
+/// when a lint is emitted, whether it's suppressed or not, and when `should_skip_lint` skips the
+/// analysis of a lint. Lints emitted without the functions of this module have to call this.
+pub fn record_suppression((level, source): LevelAndSource) {
+    if let Level::Allow | Level::Expect(_) = level
+        && let LintLevelSource::Node { span, .. } = source
+    {
+        USED_SUPPRESSIONS.with_borrow_mut(|used| used.insert(span));
+    }
+}
+
+/// Returns the spans of the lints named in `#[allow]` and `#[expect]` attributes that suppressed
+/// a lint so far.
+pub fn used_suppressions() -> FxHashSet<Span> {
+    USED_SUPPRESSIONS.with_borrow(Clone::clone)
+}
+
+/// A lint context which knows where the level of a lint at the current node comes from.
+pub trait LintLevelContext: LintContext {
+    fn lint_level_and_source(&self, lint: &'static Lint) -> LevelAndSource;
+}
+
+impl LintLevelContext for LateContext<'_> {
+    fn lint_level_and_source(&self, lint: &'static Lint) -> LevelAndSource {
+        self.tcx.lint_level_at_node(lint, self.last_node_with_lint_attrs)
+    }
+}
+
+impl LintLevelContext for EarlyContext<'_> {
+    fn lint_level_and_source(&self, lint: &'static Lint) -> LevelAndSource {
+        self.builder.lint_level(lint)
+    }
+}
+
+/// Checks if a lint at `sp` must not be emitted, and records the attribute suppressing it if any.
+/// Expected lints are still emitted, so that their expectation is fulfilled.
+fn is_filtered(sess: &Session, level: LevelAndSource, sp: &MultiSpan) -> bool {
+    let is_expected = matches!(level.0, Level::Expect(_));
+    record_suppression(level);
+    !is_expected && is_on_unchanged_lines(sess, sp)
+}
+
+/// Checks if `lint` must not be emitted at `sp`, as the functions of this module do, for lints
+/// emitted with `struct_span_lint` directly.
+pub fn is_lint_filtered(cx: &impl LintLevelContext, lint: &'static Lint, sp: Span) -> bool {
+    is_filtered(cx.sess(), cx.lint_level_and_source(lint), &sp.into())
+}
+
+/// Checks if a lint at `sp` is on lines that didn't change.
+fn is_on_unchanged_lines(sess: &Session, sp: &MultiSpan) -> bool {
+    let Some(changed) = CHANGED_LINES.get() else {
+        return false;
+    };
+    let Some(span) = sp
+        .primary_span()
+        .map(Span::source_callsite)
+        .filter(|span| !span.is_dummy())
+    else {
+        return false;
+    };
+    let source_map = sess.source_map();
+    let (lo, hi) = (
+        source_map.lookup_char_pos(span.lo()),
+        source_map.lookup_char_pos(span.hi()),
+    );
+    let FileName::Real(RealFileName::LocalPath(file)) = &lo.file.name else {
+        return false;
+    };
+    let is_changed = fs::canonicalize(file).map_or(true, |file| {
+        changed.get(&file).is_some_and(|ranges| {
+            ranges.is_empty()
+                || ranges
+                    .iter()
+                    .any(|range| *range.start() <= hi.line && lo.line <= *range.end())
+        })
+    });
+    !is_changed
+}
 
 fn docs_link(diag: &mut Diagnostic, lint: &'static Lint) {
     if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err() {
@@ -45,7 +163,11 @@ fn docs_link(diag: &mut Diagnostic, lint: &'static Lint) {
 /// 17 |     std::mem::forget(seven);
 ///    |     ^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
-pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: &str) {
+pub fn span_lint<T: LintLevelContext>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: &str) {
+    let sp = sp.into();
+    if is_filtered(cx.sess(), cx.lint_level_and_source(lint), &sp) {
+        return;
+    }
     cx.struct_span_lint(lint, sp, msg.to_string(), |diag| {
         docs_link(diag, lint);
         diag
@@ -72,7 +194,7 @@ pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<Mult
 ///    |
 ///    = help: consider using `f64::NAN` if you would like a constant representing NaN
 /// ```
-pub fn span_lint_and_help<T: LintContext>(
+pub fn span_lint_and_help<T: LintLevelContext>(
     cx: &T,
     lint: &'static Lint,
     span: impl Into<MultiSpan>,
@@ -80,6 +202,10 @@ pub fn span_lint_and_help<T: LintContext>(
     help_span: Option<Span>,
     help: &str,
 ) {
+    let span = span.into();
+    if is_filtered(cx.sess(), cx.lint_level_and_source(lint), &span) {
+        return;
+    }
     cx.struct_span_lint(lint, span, msg.to_string(), |diag| {
         let help = help.to_string();
         if let Some(help_span) = help_span {
@@ -115,7 +241,7 @@ pub fn span_lint_and_help<T: LintContext>(
 /// 10 |     forget(&SomeStruct);
 ///    |            ^^^^^^^^^^^
 /// ```
-pub fn span_lint_and_note<T: LintContext>(
+pub fn span_lint_and_note<T: LintLevelContext>(
     cx: &T,
     lint: &'static Lint,
     span: impl Into<MultiSpan>,
@@ -123,6 +249,10 @@ pub fn span_lint_and_note<T: LintContext>(
     note_span: Option<Span>,
     note: &str,
 ) {
+    let span = span.into();
+    if is_filtered(cx.sess(), cx.lint_level_and_source(lint), &span) {
+        return;
+    }
     cx.struct_span_lint(lint, span, msg.to_string(), |diag| {
         let note = note.to_string();
         if let Some(note_span) = note_span {
@@ -141,10 +271,14 @@ pub fn span_lint_and_note<T: LintContext>(
 /// If you change the signature, remember to update the internal lint `CollapsibleCalls`
 pub fn span_lint_and_then<C, S, F>(cx: &C, lint: &'static Lint, sp: S, msg: &str, f: F)
 where
-    C: LintContext,
+    C: LintLevelContext,
     S: Into<MultiSpan>,
     F: FnOnce(&mut Diagnostic),
 {
+    let sp = sp.into();
+    if is_filtered(cx.sess(), cx.lint_level_and_source(lint), &sp) {
+        return;
+    }
     cx.struct_span_lint(lint, sp, msg.to_string(), |diag| {
         f(diag);
         docs_link(diag, lint);
@@ -153,6 +287,10 @@ where
 }
 
 pub fn span_lint_hir(cx: &LateContext<'_>, lint: &'static Lint, hir_id: HirId, sp: Span, msg: &str) {
+    let sp = sp.into();
+    if is_filtered(cx.sess(), cx.tcx.lint_level_at_node(lint, hir_id), &sp) {
+        return;
+    }
     cx.tcx.struct_span_lint_hir(lint, hir_id, sp, msg.to_string(), |diag| {
         docs_link(diag, lint);
         diag
@@ -167,6 +305,10 @@ pub fn span_lint_hir_and_then(
     msg: &str,
     f: impl FnOnce(&mut Diagnostic),
 ) {
+    let sp = sp.into();
+    if is_filtered(cx.sess(), cx.tcx.lint_level_at_node(lint, hir_id), &sp) {
+        return;
+    }
     cx.tcx.struct_span_lint_hir(lint, hir_id, sp, msg.to_string(), |diag| {
         f(diag);
         docs_link(diag, lint);
@@ -194,7 +336,7 @@ pub fn span_lint_hir_and_then(
 ///     = note: `-D fold-any` implied by `-D warnings`
 /// ```
 #[cfg_attr(feature = "internal", allow(clippy::collapsible_span_lint_calls))]
-pub fn span_lint_and_sugg<T: LintContext>(
+pub fn span_lint_and_sugg<T: LintLevelContext>(
     cx: &T,
     lint: &'static Lint,
     sp: Span,
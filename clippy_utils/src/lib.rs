@@ -1796,6 +1796,20 @@ pub fn is_lint_allowed(cx: &LateContext<'_>, lint: &'static Lint, id: HirId) ->
     cx.tcx.lint_level_at_node(lint, id).0 == Level::Allow
 }
 
+/// Returns `true` if the lint is allowed at the node, so its analysis can be skipped there.
+///
+/// Unlike [`is_lint_allowed`], the attribute allowing the lint is recorded as used, see
+/// [`diagnostics::record_suppression`], as the lint might have been emitted otherwise. Only use
+/// this to check the lint which is analysed at that node.
+pub fn should_skip_lint(cx: &LateContext<'_>, lint: &'static Lint, id: HirId) -> bool {
+    let level = cx.tcx.lint_level_at_node(lint, id);
+    if level.0 != Level::Allow {
+        return false;
+    }
+    diagnostics::record_suppression(level);
+    true
+}
+
 pub fn strip_pat_refs<'hir>(mut pat: &'hir Pat<'hir>) -> &'hir Pat<'hir> {
     while let PatKind::Ref(subpat, _) = pat.kind {
         pat = subpat;
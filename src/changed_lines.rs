@@ -0,0 +1,173 @@
+//! The `--only-changed-since` and `--only-changed` options of `cargo clippy`. The changed lines are
+//! written to a file as `path:start-end` ranges with absolute paths, which is passed to
+//! `clippy-driver` with `--only-changed`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write as _;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChangedLines {
+    /// The lines changed since a git revision
+    Since(String),
+    /// A file listing the changed lines
+    File(PathBuf),
+}
+
+impl ChangedLines {
+    /// Writes the changed lines to a file named after their hash, so that cargo runs Clippy again
+    /// when they change. Returns the path of the file.
+    pub fn write(&self) -> Result<PathBuf, String> {
+        let ranges = match self {
+            Self::Since(rev) => since(rev)?,
+            Self::File(path) => {
+                let list = fs::read_to_string(path)
+                    .map_err(|e| format!("failed to read the changed lines from `{}`: {e}", path.display()))?;
+                from_list(&list, &env::current_dir().map_err(|e| e.to_string())?)?
+            },
+        };
+        let mut hasher = DefaultHasher::new();
+        ranges.hash(&mut hasher);
+        let path = env::temp_dir().join(format!("clippy-changed-lines-{:016x}", hasher.finish()));
+        fs::write(&path, ranges).map_err(|e| format!("failed to write `{}`: {e}", path.display()))?;
+        Ok(path)
+    }
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| format!("could not run git: {e}"))?;
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|e| e.to_string())
+    } else {
+        Err(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Returns the lines changed in the working tree since `rev`, including untracked files.
+fn since(rev: &str) -> Result<String, String> {
+    let root = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?.trim_end());
+    let diff = git(&[
+        "-c",
+        "core.quotePath=false",
+        "diff",
+        "--no-color",
+        "--no-ext-diff",
+        "--unified=0",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        rev,
+        "--",
+    ])?;
+    let mut ranges = from_diff(&diff, &root);
+    for file in git(&["ls-files", "--others", "--exclude-standard", "--full-name"])?.lines() {
+        let _ = writeln!(ranges, "{}", root.join(file).display());
+    }
+    Ok(ranges)
+}
+
+/// Returns the ranges of lines added or modified by a diff without context lines.
+fn from_diff(diff: &str, root: &Path) -> String {
+    let mut ranges = String::new();
+    let mut file = None;
+    let mut in_header = false;
+    for line in diff.lines() {
+        if line.starts_with("diff ") {
+            in_header = true;
+            file = None;
+        } else if let Some(path) = line.strip_prefix("+++ ").filter(|_| in_header) {
+            // Deleted files are `/dev/null`
+            file = path.strip_prefix("b/").map(|path| root.join(path));
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            in_header = false;
+            // `@@ -start,count +start,count @@`, where the count defaults to 1
+            let Some(file) = &file else { continue };
+            let Some(new) = hunk.split(' ').nth(1).and_then(|new| new.strip_prefix('+')) else {
+                continue;
+            };
+            let (start, count) = new.split_once(',').unwrap_or((new, "1"));
+            if let (Ok(start), Ok(count @ 1..)) = (start.parse::<usize>(), count.parse::<usize>()) {
+                let _ = writeln!(ranges, "{}:{start}-{}", file.display(), start + count - 1);
+            }
+        }
+    }
+    ranges
+}
+
+/// Makes the paths of a list of `path`, `path:line` or `path:start-end` lines absolute.
+fn from_list(list: &str, current_dir: &Path) -> Result<String, String> {
+    let mut ranges = String::new();
+    for line in list.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (file, range) = match line.rsplit_once(':') {
+            Some((file, range)) if range.bytes().all(|b| b.is_ascii_digit() || b == b'-') => {
+                let (start, end) = range.split_once('-').unwrap_or((range, range));
+                match (start.parse::<usize>(), end.parse::<usize>()) {
+                    (Ok(start), Ok(end)) if start <= end => (file, Some((start, end))),
+                    _ => return Err(format!("invalid range of lines `{range}` for `{file}`")),
+                }
+            },
+            _ => (line, None),
+        };
+        let file = current_dir.join(file);
+        let _ = match range {
+            Some((start, end)) => writeln!(ranges, "{}:{start}-{end}", file.display()),
+            None => writeln!(ranges, "{}", file.display()),
+        };
+    }
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_diff, from_list};
+    use std::path::Path;
+
+    #[test]
+    fn diff() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 0000000..1111111 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3 +3 @@ fn f() {
+-    old
++    new
+@@ -10,0 +11,3 @@ fn g() {
+++++ added
++
++    added
+@@ -20,2 +22,0 @@ fn h() {
+-    removed
+-    removed
+diff --git a/src/removed.rs b/src/removed.rs
+deleted file mode 100644
+--- a/src/removed.rs
++++ /dev/null
+@@ -1 +0,0 @@
+-fn removed() {}
+";
+        assert_eq!(
+            from_diff(diff, Path::new("/repo")),
+            "/repo/src/lib.rs:3-3\n/repo/src/lib.rs:11-13\n"
+        );
+    }
+
+    #[test]
+    fn list() {
+        let list = "src/lib.rs:3\nsrc/main.rs:10-20\n\nsrc/new.rs\n";
+        assert_eq!(
+            from_list(list, Path::new("/repo")).unwrap(),
+            "/repo/src/lib.rs:3-3\n/repo/src/main.rs:10-20\n/repo/src/new.rs\n"
+        );
+        assert!(from_list("src/lib.rs:20-10", Path::new("/repo")).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! A cache of the output of `clippy-driver`, enabled by setting `CLIPPY_DIAGNOSTICS_CACHE` to a
+//! directory. Cargo re-runs Clippy whenever its flags change, e.g. when switching between lint
+//! levels or between `cargo check` and `cargo clippy`, which share the same outputs.
+//!
+//! The compilation runs in a child process whose stderr is recorded along with its outputs. The
+//! entry is keyed by the arguments, the version of Clippy and the location of `clippy.toml`, and
+//! is used as long as the files and environment variables listed in the dep-info of the
+//! compilation, and the metadata of the dependencies, are unchanged. The outputs are restored and
+//! the stderr is replayed instead of running the compilation again.
+//!
+//! Entries are never removed: outdated ones stay in the directory until it is deleted.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+use crate::arg_value;
+
+const CACHE_DIR_VAR: &str = "CLIPPY_DIAGNOSTICS_CACHE";
+
+/// Environment variables that make Clippy write files that aren't listed in the dep-info
+const REPORT_VARS: [&str; 4] = [
+    "CLIPPY_UNSAFE_INVENTORY",
+    "CLIPPY_TYPE_SIZES",
+    "CLIPPY_SUPPRESSIONS",
+    "CLIPPY_STALE_ALLOWS",
+];
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    /// The files read by the compilation, with the hash of their contents
+    inputs: Vec<(PathBuf, u64)>,
+    /// The environment variables read by the compilation, with their values
+    env: Vec<(String, Option<String>)>,
+    /// The files written by the compilation, which are copied to the cache
+    outputs: Vec<PathBuf>,
+    /// What the compilation printed to stderr, including the artifact notifications
+    stderr: String,
+}
+
+impl Entry {
+    fn is_fresh(&self) -> bool {
+        self.env.iter().all(|(name, value)| env::var(name).ok() == *value)
+            && self.inputs.iter().all(|(path, hash)| hash_file(path) == Some(*hash))
+    }
+}
+
+/// Runs the compilation with the cache if it's enabled and the compilation emits dep-info.
+/// Returns the exit code of the compilation.
+pub fn run() -> Option<i32> {
+    let cache_dir = PathBuf::from(env::var_os(CACHE_DIR_VAR)?);
+    // `cargo fix` applies the suggestions of the diagnostics
+    if REPORT_VARS.iter().any(|var| env::var_os(var).is_some()) || env::var_os("__CARGO_FIX_PLZ").is_some() {
+        return None;
+    }
+    let args: Vec<String> = env::args().collect();
+    let dep_info = dep_info_path(&args)?;
+
+    let key = key(&args);
+    let entry_path = cache_dir.join(format!("{key:016x}.json"));
+    let outputs_dir = cache_dir.join(format!("{key:016x}"));
+    if let Some(entry) = load(&entry_path)
+        && entry.is_fresh()
+        && restore_outputs(&entry, &outputs_dir).is_ok()
+    {
+        let _ = io::stderr().write_all(entry.stderr.as_bytes());
+        return Some(0);
+    }
+
+    let (code, stderr) = run_child(&args);
+    if code == 0
+        && let Ok(stderr) = String::from_utf8(stderr)
+        && let Some(entry) = record(&dep_info, stderr)
+    {
+        // The cache is only an optimisation, so it's fine to fail to write it
+        let _ = store(&entry, &entry_path, &outputs_dir);
+    }
+    Some(code)
+}
+
+/// Returns the path of the dep-info file emitted by the compilation.
+fn dep_info_path(args: &[String]) -> Option<PathBuf> {
+    let emit = arg_value(args, "--emit", |emit| {
+        emit.split(',').any(|kind| kind.starts_with("dep-info"))
+    })?;
+    let dep_info = emit.split(',').find(|kind| kind.starts_with("dep-info"))?;
+    if let Some(path) = dep_info.strip_prefix("dep-info=") {
+        return Some(path.into());
+    }
+    let out_dir = arg_value(args, "--out-dir", |_| true)?;
+    let crate_name = arg_value(args, "--crate-name", |_| true)?;
+    let extra_filename = arg_value(args, "-C", |opt| opt.starts_with("extra-filename="))
+        .and_then(|opt| opt.strip_prefix("extra-filename="))
+        .unwrap_or_default();
+    Some(Path::new(out_dir).join(format!("{crate_name}{extra_filename}.d")))
+}
+
+/// Hashes what the output depends on that isn't listed in the dep-info of the compilation.
+fn key(args: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    args.hash(&mut hasher);
+    env::var("CLIPPY_ARGS").ok().hash(&mut hasher);
+    env::current_dir().ok().hash(&mut hasher);
+    rustc_tools_util::get_version_info!().to_string().hash(&mut hasher);
+    if let Ok(exe) = env::current_exe()
+        && let Ok(metadata) = fs::metadata(exe)
+    {
+        (metadata.len(), metadata.modified().ok()).hash(&mut hasher);
+    }
+    if let Ok((conf, _)) = clippy_lints::lookup_conf_file() {
+        conf.hash(&mut hasher);
+    }
+    // The metadata of the dependencies, as rebuilding a dependency may change the diagnostics
+    for (flag, value) in args.iter().zip(args.iter().skip(1)) {
+        if flag == "--extern"
+            && let Some((_, path)) = value.split_once('=')
+        {
+            hash_file(Path::new(path)).hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+fn hash_file(path: &Path) -> Option<u64> {
+    let contents = fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Runs the compilation in a child process, forwarding its stderr. Returns its exit code and
+/// stderr.
+fn run_child(args: &[String]) -> (i32, Vec<u8>) {
+    let mut child = Command::new(env::current_exe().expect("current executable path invalid"))
+        .args(&args[1..])
+        .env_remove(CACHE_DIR_VAR)
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("could not run clippy-driver");
+
+    let mut child_stderr = child.stderr.take().expect("the stderr of the child is piped");
+    let mut stderr = Vec::new();
+    let mut buf = [0; 8192];
+    // Forward the output as it comes, cargo starts building the dependents of the crate as soon
+    // as its metadata is emitted
+    while let Ok(len @ 1..) = child_stderr.read(&mut buf) {
+        let _ = io::stderr().write_all(&buf[..len]);
+        stderr.extend_from_slice(&buf[..len]);
+    }
+
+    let code = child.wait().ok().and_then(|status| status.code()).unwrap_or(1);
+    (code, stderr)
+}
+
+/// Creates the entry of a compilation from its dep-info and stderr.
+fn record(dep_info: &Path, stderr: String) -> Option<Entry> {
+    let (inputs, env) = parse_dep_info(&fs::read_to_string(dep_info).ok()?);
+    // The outputs listed in the dep-info don't match the names of the metadata files
+    let mut outputs = artifacts(&stderr);
+    if outputs.is_empty() {
+        return None;
+    }
+    outputs.push(dep_info.to_path_buf());
+    Some(Entry {
+        inputs: inputs
+            .into_iter()
+            .map(|path| hash_file(&path).map(|hash| (path, hash)))
+            .collect::<Option<_>>()?,
+        env: env
+            .into_iter()
+            .map(|name| {
+                let value = env::var(&name).ok();
+                (name, value)
+            })
+            .collect(),
+        outputs,
+        stderr,
+    })
+}
+
+/// Returns the files listed in the artifact notifications of the JSON output.
+fn artifacts(stderr: &str) -> Vec<PathBuf> {
+    #[derive(Deserialize)]
+    struct Artifact {
+        artifact: PathBuf,
+    }
+
+    stderr
+        .lines()
+        .filter(|line| line.contains(r#""artifact":"#))
+        .filter_map(|line| serde_json::from_str::<Artifact>(line).ok())
+        .map(|notification| notification.artifact)
+        .collect()
+}
+
+/// Parses a dep-info file into the files and the environment variables the compilation depends
+/// on.
+fn parse_dep_info(dep_info: &str) -> (Vec<PathBuf>, Vec<String>) {
+    let mut inputs = Vec::new();
+    let mut env = Vec::new();
+    for line in dep_info.lines() {
+        if let Some(var) = line.strip_prefix("# env-dep:") {
+            env.push(var.split('=').next().unwrap_or(var).to_string());
+        } else if let Some((_, deps)) = line.split_once(": ") {
+            for dep in split_deps(deps) {
+                if !inputs.contains(&dep) {
+                    inputs.push(dep);
+                }
+            }
+        }
+    }
+    (inputs, env)
+}
+
+/// Splits the dependencies of a rule, in which spaces are escaped with `\`.
+fn split_deps(deps: &str) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let mut path = String::new();
+    let mut chars = deps.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.as_str().starts_with(' ') => {
+                path.push(' ');
+                chars.next();
+            },
+            ' ' => {
+                if !path.is_empty() {
+                    paths.push(PathBuf::from(std::mem::take(&mut path)));
+                }
+            },
+            c => path.push(c),
+        }
+    }
+    if !path.is_empty() {
+        paths.push(PathBuf::from(path));
+    }
+    paths
+}
+
+fn load(path: &Path) -> Option<Entry> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+fn store(entry: &Entry, entry_path: &Path, outputs_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(outputs_dir)?;
+    for (i, output) in entry.outputs.iter().enumerate() {
+        fs::copy(output, outputs_dir.join(i.to_string()))?;
+    }
+    // Write the entry last and atomically, so that it's never used with missing outputs
+    let tmp = entry_path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp, serde_json::to_vec(entry)?)?;
+    fs::rename(tmp, entry_path)
+}
+
+fn restore_outputs(entry: &Entry, outputs_dir: &Path) -> io::Result<()> {
+    for (i, output) in entry.outputs.iter().enumerate() {
+        fs::copy(outputs_dir.join(i.to_string()), output)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_dep_info() {
+    let dep_info = "\
+/target/debug/deps/libfoo-123.rmeta: src/lib.rs src/with\\ space.rs Cargo.toml
+
+/target/debug/deps/foo-123.d: src/lib.rs src/with\\ space.rs Cargo.toml
+
+src/lib.rs:
+src/with\\ space.rs:
+Cargo.toml:
+
+# env-dep:CLIPPY_ARGS=-W__CLIPPY_HACKERY__clippy::pedantic
+# env-dep:CLIPPY_CONF_DIR
+";
+    let (inputs, env) = parse_dep_info(dep_info);
+    assert_eq!(
+        inputs,
+        [
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("src/with space.rs"),
+            PathBuf::from("Cargo.toml")
+        ]
+    );
+    assert_eq!(env, ["CLIPPY_ARGS", "CLIPPY_CONF_DIR"]);
+}
+
+#[test]
+fn test_artifacts() {
+    let stderr = r#"{"$message_type":"diagnostic","message":"unneeded `return` statement"}
+{"$message_type":"artifact","artifact":"/target/debug/deps/libfoo-123.rmeta","emit":"metadata"}
+"#;
+    assert_eq!(
+        artifacts(stderr),
+        [PathBuf::from("/target/debug/deps/libfoo-123.rmeta")]
+    );
+}
+
+#[test]
+fn test_dep_info_path() {
+    let args: Vec<String> = "clippy-driver rustc --crate-name foo --emit=dep-info,metadata -C extra-filename=-123 --out-dir /target/debug/deps"
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    assert_eq!(
+        dep_info_path(&args),
+        Some(PathBuf::from("/target/debug/deps/foo-123.d"))
+    );
+}
@@ -12,19 +12,27 @@
 // (Currently there is no way to opt into sysroot crates without `extern crate`.)
 extern crate rustc_driver;
 extern crate rustc_interface;
+extern crate rustc_lint;
 extern crate rustc_session;
 extern crate rustc_span;
 
+use clippy_lints::plugin::{Declaration, Registry};
+use clippy_lints::Conf;
+use libloading::Library;
 use rustc_interface::interface;
+use rustc_lint::LintStore;
 use rustc_session::config::ErrorOutputType;
 use rustc_session::parse::ParseSess;
-use rustc_session::EarlyErrorHandler;
+use rustc_session::{EarlyErrorHandler, Session};
 use rustc_span::symbol::Symbol;
 
 use std::env;
 use std::ops::Deref;
 use std::path::Path;
 use std::process::exit;
+use std::sync::Mutex;
+
+mod diagnostics_cache;
 
 /// If a command-line option matches `find_arg`, then apply the predicate `pred` on its value. If
 /// true, then return it. The parameter is assumed to be either `--arg=value` or `--arg value`.
@@ -70,6 +78,101 @@ fn track_clippy_args(parse_sess: &mut ParseSess, args_env_var: &Option<String>)
     ));
 }
 
+/// Track an environment variable read by Clippy so that cargo will re-run clippy when it changes,
+/// e.g. when a report is requested for crates that were already checked
+fn track_env_var(parse_sess: &mut ParseSess, name: &str) {
+    parse_sess.env_depinfo.get_mut().insert((
+        Symbol::intern(name),
+        env::var(name).ok().map(|value| Symbol::intern(&value)),
+    ));
+}
+
+/// The loaded plugins. The lints and passes they registered live in the libraries, so they are
+/// never unloaded.
+static PLUGINS: Mutex<Vec<Library>> = Mutex::new(Vec::new());
+
+/// Loads the lint plugins listed in the `plugins` configuration option and in `CLIPPY_PLUGINS`,
+/// and lets them register their lints
+fn load_plugins(sess: &Session, lint_store: &mut LintStore, conf: &Conf, conf_dir: Option<&Path>) {
+    let from_env = env::var_os("CLIPPY_PLUGINS");
+    // Paths from the configuration are relative to its directory, but shown as they were written
+    let paths = conf
+        .plugins
+        .iter()
+        .map(|path| {
+            (
+                path.clone(),
+                conf_dir.map_or_else(|| path.clone(), |dir| dir.join(path)),
+            )
+        })
+        .chain(
+            from_env
+                .iter()
+                .flat_map(env::split_paths)
+                .map(|path| (path.clone(), path)),
+        );
+    for (configured_path, path) in paths {
+        // Re-run clippy when a plugin is rebuilt
+        if let Some(path) = path.to_str() {
+            sess.parse_sess.file_depinfo.borrow_mut().insert(Symbol::intern(path));
+        }
+
+        // SAFETY: plugins are required to be built with the same toolchain and against the same
+        // sources of `clippy_lints`. The version of the plugin interface, the commit of the
+        // compiler and the fingerprint of `clippy_lints` are checked before calling into the plugin.
+        if let Err(error) = unsafe { load_plugin(sess, lint_store, conf, &path) } {
+            sess.err(format!(
+                "could not load Clippy plugin `{}`: {error}",
+                configured_path.display()
+            ));
+        }
+    }
+}
+
+unsafe fn load_plugin(sess: &Session, lint_store: &mut LintStore, conf: &Conf, path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err("the file doesn't exist".to_string());
+    }
+    let library = Library::new(path).map_err(|error| error.to_string())?;
+
+    let api_version = **library
+        .get::<*const u32>(clippy_lints::plugin::API_VERSION_SYMBOL)
+        .map_err(|_| "it doesn't declare a plugin with `declare_clippy_plugin!`".to_string())?;
+    if api_version != clippy_lints::plugin::API_VERSION {
+        return Err(format!(
+            "it uses version {api_version} of the plugin interface, but this Clippy uses version {}",
+            clippy_lints::plugin::API_VERSION
+        ));
+    }
+
+    let declaration = &**library
+        .get::<*const Declaration>(clippy_lints::plugin::DECLARATION_SYMBOL)
+        .map_err(|error| error.to_string())?;
+    // The commit is unknown if `rustc -vV` failed while building Clippy, no plugin can be checked then
+    if clippy_lints::plugin::RUSTC_COMMIT_HASH == "unknown" {
+        return Err("the rustc commit this Clippy was built with is unknown".to_string());
+    }
+    if declaration.rustc_commit_hash != clippy_lints::plugin::RUSTC_COMMIT_HASH {
+        return Err(format!(
+            "it was built with rustc {}, but this Clippy was built with rustc {}",
+            declaration.rustc_commit_hash,
+            clippy_lints::plugin::RUSTC_COMMIT_HASH
+        ));
+    }
+    if declaration.clippy_lints_fingerprint != clippy_lints::plugin::CLIPPY_LINTS_FINGERPRINT {
+        return Err(format!(
+            "it was built against `clippy_lints` with fingerprint {}, but this Clippy has fingerprint {}",
+            declaration.clippy_lints_fingerprint,
+            clippy_lints::plugin::CLIPPY_LINTS_FINGERPRINT
+        ));
+    }
+
+    (declaration.register)(&mut Registry { sess, lint_store, conf });
+
+    PLUGINS.lock().unwrap().push(library);
+    Ok(())
+}
+
 /// Track files that may be accessed at runtime in `file_depinfo` so that cargo will re-run clippy
 /// when any of them are modified
 fn track_files(parse_sess: &mut ParseSess) {
@@ -112,6 +215,31 @@ impl rustc_driver::Callbacks for RustcCallbacks {
         let clippy_args_var = self.clippy_args_var.take();
         config.parse_sess_created = Some(Box::new(move |parse_sess| {
             track_clippy_args(parse_sess, &clippy_args_var);
+            track_env_var(parse_sess, "CLIPPY_UNSAFE_INVENTORY");
+        }));
+    }
+}
+
+/// Only runs the pass writing the inventory of `unsafe` code, for dependencies checked while
+/// `CLIPPY_UNSAFE_INVENTORY` is set.
+struct UnsafeInventoryCallbacks {
+    clippy_args_var: Option<String>,
+}
+
+impl rustc_driver::Callbacks for UnsafeInventoryCallbacks {
+    fn config(&mut self, config: &mut interface::Config) {
+        let previous = config.register_lints.take();
+        let clippy_args_var = self.clippy_args_var.take();
+        config.parse_sess_created = Some(Box::new(move |parse_sess| {
+            track_clippy_args(parse_sess, &clippy_args_var);
+            track_env_var(parse_sess, "CLIPPY_UNSAFE_INVENTORY");
+        }));
+        config.register_lints = Some(Box::new(move |sess, lint_store| {
+            if let Some(previous) = &previous {
+                (previous)(sess, lint_store);
+            }
+
+            clippy_lints::register_unsafe_inventory(lint_store);
         }));
     }
 }
@@ -129,6 +257,11 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
         let clippy_args_var = self.clippy_args_var.take();
         config.parse_sess_created = Some(Box::new(move |parse_sess| {
             track_clippy_args(parse_sess, &clippy_args_var);
+            track_env_var(parse_sess, "CLIPPY_UNSAFE_INVENTORY");
+            track_env_var(parse_sess, "CLIPPY_TYPE_SIZES");
+            track_env_var(parse_sess, "CLIPPY_SUPPRESSIONS");
+            track_env_var(parse_sess, "CLIPPY_STALE_ALLOWS");
+            track_env_var(parse_sess, "CLIPPY_PLUGINS");
             track_files(parse_sess);
 
             // Trigger a rebuild if CLIPPY_CONF_DIR changes. The value must be a valid string so
@@ -149,6 +282,9 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
             clippy_lints::register_plugins(lint_store, sess, &conf);
             clippy_lints::register_pre_expansion_lints(lint_store, sess, &conf);
             clippy_lints::register_renamed(lint_store);
+
+            let conf_dir = conf_path.as_ref().ok().and_then(|(path, _)| path.as_deref()?.parent());
+            load_plugins(sess, lint_store, &conf, conf_dir);
         }));
 
         // FIXME: #4825; This is required, because Clippy lints that are based on MIR have to be
@@ -258,6 +394,7 @@ pub fn main() {
         pass_sysroot_env_if_given(&mut args, sys_root_env);
 
         let mut no_deps = false;
+        let mut only_changed = None;
         let clippy_args_var = env::var("CLIPPY_ARGS").ok();
         let clippy_args = clippy_args_var
             .as_deref()
@@ -269,6 +406,10 @@ pub fn main() {
                     no_deps = true;
                     None
                 },
+                _ if s.starts_with("--only-changed=") => {
+                    only_changed = s.strip_prefix("--only-changed=");
+                    None
+                },
                 _ => Some(s.to_string()),
             })
             .chain(vec!["--cfg".into(), r#"feature="cargo-clippy""#.into()])
@@ -279,14 +420,26 @@ pub fn main() {
         // - IF Clippy is run on the main crate, not on deps (`!cap_lints_allow`) THEN
         //    - IF `--no-deps` is not set (`!no_deps`) OR
         //    - IF `--no-deps` is set and Clippy is run on the specified primary package
+        // On deps, only the inventory of `unsafe` code is collected, if it was requested
         let cap_lints_allow = arg_value(&orig_args, "--cap-lints", |val| val == "allow").is_some()
             && arg_value(&orig_args, "--force-warn", |val| val.contains("clippy::")).is_none();
         let in_primary_package = env::var("CARGO_PRIMARY_PACKAGE").is_ok();
+        let unsafe_inventory = env::var_os("CLIPPY_UNSAFE_INVENTORY").is_some();
 
         let clippy_enabled = !cap_lints_allow && (!no_deps || in_primary_package);
         if clippy_enabled {
+            if let Some(path) = only_changed
+                && let Err(e) = clippy_lints::emit_only_on_changed_lines(Path::new(path))
+            {
+                handler.early_error(format!("failed to read the changed lines from `{path}`: {e}"));
+            }
+            if let Some(code) = diagnostics_cache::run() {
+                exit(code);
+            }
             args.extend(clippy_args);
             rustc_driver::RunCompiler::new(&args, &mut ClippyCallbacks { clippy_args_var }).run()
+        } else if cap_lints_allow && unsafe_inventory && !no_deps {
+            rustc_driver::RunCompiler::new(&args, &mut UnsafeInventoryCallbacks { clippy_args_var }).run()
         } else {
             rustc_driver::RunCompiler::new(&args, &mut RustcCallbacks { clippy_args_var }).run()
         }
@@ -6,6 +6,12 @@ use std::env;
 use std::path::PathBuf;
 use std::process::{self, Command};
 
+use changed_lines::ChangedLines;
+use summary::Options as SummaryOptions;
+
+mod changed_lines;
+mod summary;
+
 const CARGO_CLIPPY_HELP: &str = "Checks a package to catch common mistakes and improve your Rust code.
 
 Usage:
@@ -17,6 +23,13 @@ Common options:
     -h, --help               Print this message
     -V, --version            Print version info and exit
     --explain LINT           Print the documentation for a given lint
+    --summary                Print the number of lints emitted for each lint and crate after the build
+    --summary-json PATH      Also write the summary to PATH as JSON. This flag implies `--summary`
+    --fail-on GROUPS         Only fail if lints of the comma-separated Clippy lint GROUPS, e.g. `correctness`,
+                             are emitted or if the build has other errors. This flag implies `--summary`
+    --only-changed-since REV Only emit the lints on the lines changed since the git revision REV,
+                             including uncommitted changes and untracked files
+    --only-changed FILE      Only emit the lints on the lines listed in FILE, one `path[:start[-end]]` per line
 
 For the other options see `cargo check --help`.
 
@@ -75,6 +88,8 @@ struct ClippyCmd {
     cargo_subcommand: &'static str,
     args: Vec<String>,
     clippy_args: Vec<String>,
+    summary: Option<SummaryOptions>,
+    only_changed: Option<ChangedLines>,
 }
 
 impl ClippyCmd {
@@ -85,8 +100,31 @@ impl ClippyCmd {
         let mut cargo_subcommand = "check";
         let mut args = vec![];
         let mut clippy_args: Vec<String> = vec![];
+        let mut summary: Option<SummaryOptions> = None;
+        let mut only_changed = None;
+
+        while let Some(arg) = old_args.next() {
+            if let Some(path) = option_value(&arg, "--summary-json", &mut old_args) {
+                summary.get_or_insert_with(SummaryOptions::default).json = Some(path.into());
+                continue;
+            }
+            if let Some(groups) = option_value(&arg, "--fail-on", &mut old_args) {
+                summary.get_or_insert_with(SummaryOptions::default).fail_on.extend(
+                    groups
+                        .split(',')
+                        .map(|group| group.trim().trim_start_matches("clippy::").to_string()),
+                );
+                continue;
+            }
+            if let Some(rev) = option_value(&arg, "--only-changed-since", &mut old_args) {
+                only_changed = Some(ChangedLines::Since(rev));
+                continue;
+            }
+            if let Some(path) = option_value(&arg, "--only-changed", &mut old_args) {
+                only_changed = Some(ChangedLines::File(path.into()));
+                continue;
+            }
 
-        for arg in old_args.by_ref() {
             match arg.as_str() {
                 "--fix" => {
                     cargo_subcommand = "fix";
@@ -96,6 +134,10 @@ impl ClippyCmd {
                     clippy_args.push("--no-deps".into());
                     continue;
                 },
+                "--summary" => {
+                    summary.get_or_insert_with(SummaryOptions::default);
+                    continue;
+                },
                 "--" => break,
                 _ => {},
             }
@@ -112,6 +154,8 @@ impl ClippyCmd {
             cargo_subcommand,
             args,
             clippy_args,
+            summary,
+            only_changed,
         }
     }
 
@@ -137,7 +181,17 @@ impl ClippyCmd {
         // Currently, `CLIPPY_TERMINAL_WIDTH` is used only to format "unknown field" error messages.
         let terminal_width = termize::dimensions().map_or(0, |(w, _)| w);
 
-        cmd.env("RUSTC_WORKSPACE_WRAPPER", Self::path())
+        // Cargo only runs `RUSTC_WORKSPACE_WRAPPER` on the members of the workspace, the other
+        // dependencies have to be checked with `RUSTC_WRAPPER` to be part of the unsafe inventory
+        let wrapper = if env::var_os("CLIPPY_UNSAFE_INVENTORY").is_some()
+            && !self.clippy_args.iter().any(|arg| arg == "--no-deps")
+        {
+            "RUSTC_WRAPPER"
+        } else {
+            "RUSTC_WORKSPACE_WRAPPER"
+        };
+
+        cmd.env(wrapper, Self::path())
             .env("CLIPPY_ARGS", clippy_args)
             .env("CLIPPY_TERMINAL_WIDTH", terminal_width.to_string())
             .arg(self.cargo_subcommand)
@@ -147,14 +201,56 @@ impl ClippyCmd {
     }
 }
 
+/// Returns the value of the option `name` if `arg` is `name=value`, or if `arg` is `name` and the
+/// value is the next argument.
+fn option_value(arg: &str, name: &str, args: &mut impl Iterator<Item = String>) -> Option<String> {
+    match arg.strip_prefix(name)? {
+        "" => args.next(),
+        value => value.strip_prefix('=').map(String::from),
+    }
+}
+
 fn process<I>(old_args: I) -> Result<(), i32>
 where
     I: Iterator<Item = String>,
 {
-    let cmd = ClippyCmd::new(old_args);
+    let mut cmd = ClippyCmd::new(old_args);
 
-    let mut cmd = cmd.into_std_cmd();
+    let summary = cmd.summary.take();
+    if let Some(summary) = &summary {
+        if cmd.args.iter().any(|arg| arg.starts_with("--message-format")) {
+            eprintln!("error: `--summary` can't be used with `--message-format`");
+            return Err(1);
+        }
+        if let Some(group) = summary.fail_on.iter().find(|group| !clippy_lints::is_lint_group(group)) {
+            eprintln!("error: `{group}` passed to `--fail-on` is not a Clippy lint group");
+            return Err(1);
+        }
+    }
 
+    let changed_lines = match cmd.only_changed.take().map(|only_changed| only_changed.write()) {
+        Some(Ok(path)) => Some(path),
+        Some(Err(e)) => {
+            eprintln!("error: {e}");
+            return Err(1);
+        },
+        None => None,
+    };
+    if let Some(path) = &changed_lines {
+        cmd.clippy_args.push(format!("--only-changed={}", path.display()));
+    }
+
+    let result = match &summary {
+        Some(summary) => summary::run(cmd.into_std_cmd(), summary),
+        None => run(cmd.into_std_cmd()),
+    };
+    if let Some(path) = changed_lines {
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+fn run(mut cmd: Command) -> Result<(), i32> {
     let exit_status = cmd
         .spawn()
         .expect("could not run cargo")
@@ -170,7 +266,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::ClippyCmd;
+    use super::{ChangedLines, ClippyCmd};
 
     #[test]
     fn fix() {
@@ -196,6 +292,45 @@ mod tests {
         assert_eq!(cmd.clippy_args.iter().filter(|arg| *arg == "--no-deps").count(), 1);
     }
 
+    #[test]
+    fn summary() {
+        let args = "cargo clippy --summary --fail-on correctness,clippy::suspicious --summary-json=summary.json"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        let summary = cmd.summary.unwrap();
+        assert_eq!(summary.fail_on, ["correctness", "suspicious"]);
+        assert_eq!(summary.json.unwrap().to_str(), Some("summary.json"));
+        assert_eq!(cmd.args, ["cargo", "clippy"]);
+    }
+
+    #[test]
+    fn fail_on_implies_summary() {
+        let args = "cargo clippy --fail-on=correctness -- -D warnings"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.summary.unwrap().fail_on, ["correctness"]);
+        assert_eq!(cmd.clippy_args, ["-D", "warnings"]);
+    }
+
+    #[test]
+    fn only_changed() {
+        let args = "cargo clippy --only-changed-since main --fix"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.only_changed, Some(ChangedLines::Since("main".into())));
+        assert_eq!("fix", cmd.cargo_subcommand);
+
+        let args = "cargo clippy --only-changed=lines.txt"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.only_changed, Some(ChangedLines::File("lines.txt".into())));
+        assert_eq!(cmd.args, ["cargo", "clippy"]);
+    }
+
     #[test]
     fn check() {
         let args = "cargo clippy".split_whitespace().map(ToString::to_string);
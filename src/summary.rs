@@ -0,0 +1,314 @@
+//! The `--summary` mode of `cargo clippy`. The diagnostics of every crate are read from the JSON
+//! messages of cargo, printed as usual and counted by lint and by crate.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, BufReader, IsTerminal};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
+use cargo_metadata::{CompilerMessage, Message, PackageId};
+use serde::Serialize;
+
+#[derive(Debug, Default)]
+pub struct Options {
+    /// The file to write the summary to as JSON
+    pub json: Option<PathBuf>,
+    /// The lint groups whose lints make the run fail, e.g. `correctness`
+    pub fail_on: Vec<String>,
+}
+
+#[derive(Default, Serialize)]
+struct Summary {
+    lints: BTreeMap<String, LintSummary>,
+    /// The number of lints emitted in each crate, by package name and target kind
+    crates: BTreeMap<String, usize>,
+    /// The number of errors that aren't lints, e.g. type errors
+    errors: usize,
+    #[serde(skip)]
+    lint_errors: usize,
+}
+
+#[derive(Serialize)]
+struct LintSummary {
+    /// The group of Clippy lints
+    group: Option<&'static str>,
+    count: usize,
+    crates: BTreeMap<String, usize>,
+}
+
+impl Summary {
+    fn add(&mut self, krate: &str, diagnostic: &Diagnostic) {
+        let is_error = matches!(diagnostic.level, DiagnosticLevel::Error | DiagnosticLevel::Ice);
+        match &diagnostic.code {
+            Some(code) if !is_error_code(&code.code) => {
+                let lint = self.lints.entry(code.code.clone()).or_insert_with(|| LintSummary {
+                    group: clippy_lints::lint_group(&code.code),
+                    count: 0,
+                    crates: BTreeMap::new(),
+                });
+                lint.count += 1;
+                *lint.crates.entry(krate.to_string()).or_default() += 1;
+                *self.crates.entry(krate.to_string()).or_default() += 1;
+                if is_error {
+                    self.lint_errors += 1;
+                }
+            },
+            // `aborting due to N previous errors` repeats the other errors
+            _ if is_error && !diagnostic.message.starts_with("aborting due to") => self.errors += 1,
+            _ => {},
+        }
+    }
+
+    fn print(&self) {
+        eprintln!();
+        if self.lints.is_empty() {
+            eprintln!("Summary: no lints were emitted");
+        } else {
+            let mut lints: Vec<_> = self.lints.iter().collect();
+            lints.sort_by(|(_, a), (_, b)| b.count.cmp(&a.count));
+            print_table(
+                ["Lint", "Group", "Count"],
+                lints.into_iter().map(|(name, lint)| {
+                    [
+                        name.clone(),
+                        lint.group.unwrap_or("-").to_string(),
+                        lint.count.to_string(),
+                    ]
+                }),
+            );
+            eprintln!();
+
+            let mut crates: Vec<_> = self.crates.iter().collect();
+            crates.sort_by(|(_, a), (_, b)| b.cmp(a));
+            print_table(
+                ["Crate", "Count"],
+                crates
+                    .into_iter()
+                    .map(|(name, count)| [name.clone(), count.to_string()]),
+            );
+        }
+        if self.errors > 0 {
+            eprintln!();
+            eprintln!("{} other error(s) were emitted", self.errors);
+        }
+    }
+
+    /// Returns the result of the run, given the result of cargo and the groups passed to
+    /// `--fail-on`.
+    fn result(&self, fail_on: &[String], cargo_result: Result<(), i32>) -> Result<(), i32> {
+        // The exit status of cargo is kept if the build failed for another reason than lints
+        if fail_on.is_empty() || (cargo_result.is_err() && (self.errors > 0 || self.lint_errors == 0)) {
+            return cargo_result;
+        }
+
+        let failing: Vec<&str> = self
+            .lints
+            .iter()
+            .filter(|(_, lint)| lint.group.is_some_and(|group| fail_on.iter().any(|g| g == group)))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if failing.is_empty() {
+            Ok(())
+        } else {
+            eprintln!(
+                "error: lints of the groups passed to `--fail-on` were emitted: {}",
+                failing.join(", ")
+            );
+            Err(1)
+        }
+    }
+}
+
+/// Returns the name of the crate a message belongs to, e.g. `foo (lib)`. The package name is used
+/// rather than the target name, as different packages can have targets with the same name.
+fn crate_name(message: &CompilerMessage) -> String {
+    format!(
+        "{} ({})",
+        package_name(&message.package_id),
+        message.target.kind.join(", ")
+    )
+}
+
+/// Returns the name of a package from its ID, either `name version (source)` or a package ID spec
+/// like `source#name@version`, where the name can be left out if it's the last segment of the URL.
+fn package_name(id: &PackageId) -> &str {
+    let repr = id.repr.as_str();
+    if let Some((name, _)) = repr.split_once(' ') {
+        return name;
+    }
+    let (url, fragment) = repr.rsplit_once('#').unwrap_or((repr, ""));
+    if let Some((name, _)) = fragment.split_once('@') {
+        name
+    } else {
+        let path = url.split('?').next().unwrap_or(url);
+        path.rsplit('/').next().unwrap_or(path)
+    }
+}
+
+/// Checks if `code` is an error code like `E0308`, as opposed to the name of a lint.
+fn is_error_code(code: &str) -> bool {
+    code.strip_prefix('E')
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Prints a table to stderr, with the last column aligned to the right.
+fn print_table<const N: usize>(header: [&str; N], rows: impl Iterator<Item = [String; N]>) {
+    let rows: Vec<[String; N]> = std::iter::once(header.map(String::from)).chain(rows).collect();
+    let widths: Vec<usize> = (0..N)
+        .map(|i| rows.iter().map(|row| row[i].len()).max().unwrap_or(0))
+        .collect();
+    for row in &rows {
+        let mut line = String::new();
+        for (i, (cell, width)) in row.iter().zip(&widths).enumerate() {
+            let _ = if i == N - 1 {
+                write!(line, "{cell:>width$}")
+            } else {
+                write!(line, "{cell:<width$}  ")
+            };
+        }
+        eprintln!("{line}");
+    }
+}
+
+/// Runs `cmd`, a `cargo check` or `cargo fix` command, and prints the summary of its diagnostics.
+pub fn run(mut cmd: Command, options: &Options) -> Result<(), i32> {
+    let format = if io::stderr().is_terminal() {
+        "json-diagnostic-rendered-ansi"
+    } else {
+        "json"
+    };
+    cmd.arg(format!("--message-format={format}")).stdout(Stdio::piped());
+
+    let mut child = cmd.spawn().expect("could not run cargo");
+    let stdout = child.stdout.take().expect("the stdout of cargo is piped");
+    let mut summary = Summary::default();
+    for message in Message::parse_stream(BufReader::new(stdout)) {
+        match message.expect("failed to read the output of cargo") {
+            Message::CompilerMessage(message) => {
+                if let Some(rendered) = &message.message.rendered {
+                    eprint!("{rendered}");
+                }
+                summary.add(&crate_name(&message), &message.message);
+            },
+            Message::TextLine(line) => println!("{line}"),
+            _ => {},
+        }
+    }
+    let exit_status = child.wait().expect("failed to wait for cargo?");
+
+    summary.print();
+    if let Some(path) = &options.json {
+        let json = serde_json::to_string_pretty(&summary).expect("failed to serialize the summary");
+        if let Err(e) = fs::write(path, json) {
+            eprintln!("error: failed to write the summary to `{}`: {e}", path.display());
+            return Err(1);
+        }
+    }
+
+    let cargo_result = if exit_status.success() {
+        Ok(())
+    } else {
+        Err(exit_status.code().unwrap_or(-1))
+    };
+    summary.result(&options.fail_on, cargo_result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{package_name, Summary};
+    use cargo_metadata::diagnostic::Diagnostic;
+    use cargo_metadata::PackageId;
+    use serde_json::json;
+
+    fn diagnostic(level: &str, code: Option<&str>, message: &str) -> Diagnostic {
+        serde_json::from_value(json!({
+            "message": message,
+            "code": code.map(|code| json!({ "code": code, "explanation": null })),
+            "level": level,
+            "spans": [],
+            "children": [],
+            "rendered": null,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn add() {
+        let mut summary = Summary::default();
+        summary.add(
+            "foo (lib)",
+            &diagnostic("warning", Some("clippy::needless_return"), "unneeded `return`"),
+        );
+        summary.add(
+            "foo (bin)",
+            &diagnostic("warning", Some("clippy::needless_return"), "unneeded `return`"),
+        );
+        summary.add(
+            "foo (lib)",
+            &diagnostic("error", Some("clippy::absurd_extreme_comparisons"), "..."),
+        );
+        summary.add("foo (lib)", &diagnostic("error", Some("E0308"), "mismatched types"));
+        summary.add(
+            "foo (lib)",
+            &diagnostic("error", None, "aborting due to 2 previous errors"),
+        );
+        summary.add("foo (lib)", &diagnostic("warning", None, "unused variable"));
+
+        let needless_return = &summary.lints["clippy::needless_return"];
+        assert_eq!(needless_return.group, Some("style"));
+        assert_eq!(needless_return.count, 2);
+        assert_eq!(needless_return.crates["foo (bin)"], 1);
+        assert_eq!(
+            summary.lints["clippy::absurd_extreme_comparisons"].group,
+            Some("correctness")
+        );
+        assert_eq!(summary.lints.len(), 2);
+        assert_eq!(summary.crates["foo (lib)"], 2);
+        assert_eq!(summary.crates["foo (bin)"], 1);
+        assert_eq!(summary.errors, 1);
+        assert_eq!(summary.lint_errors, 1);
+    }
+
+    #[test]
+    fn fail_on() {
+        let fail_on = ["correctness".to_string()];
+        let mut summary = Summary::default();
+        summary.add(
+            "foo (lib)",
+            &diagnostic("error", Some("clippy::needless_return"), "..."),
+        );
+        // Without `--fail-on`, the exit status of cargo is kept
+        assert_eq!(summary.result(&[], Err(101)), Err(101));
+        // Lints of other groups don't fail, whatever their level
+        assert_eq!(summary.result(&fail_on, Err(101)), Ok(()));
+
+        summary.add(
+            "foo (lib)",
+            &diagnostic("warning", Some("clippy::absurd_extreme_comparisons"), "..."),
+        );
+        assert_eq!(summary.result(&fail_on, Ok(())), Err(1));
+
+        // Other errors fail with the exit status of cargo
+        summary.add("foo (lib)", &diagnostic("error", Some("E0308"), "mismatched types"));
+        assert_eq!(summary.result(&fail_on, Err(101)), Err(101));
+
+        // A build failing without any error, e.g. a missing dependency, keeps failing
+        assert_eq!(Summary::default().result(&fail_on, Err(101)), Err(101));
+    }
+
+    #[test]
+    fn package_names() {
+        let name = |repr: &str| package_name(&PackageId { repr: repr.to_string() }).to_string();
+        assert_eq!(name("foo 0.1.0 (path+file:///ws/foo)"), "foo");
+        assert_eq!(name("path+file:///ws/crates/foo#0.1.0"), "foo");
+        assert_eq!(name("path+file:///ws/crates/foo-dir#foo@0.1.0"), "foo");
+        assert_eq!(name("git+https://github.com/org/repo?branch=dev#bar@0.2.0"), "bar");
+        assert_eq!(
+            name("registry+https://github.com/rust-lang/crates.io-index#serde@1.0.0"),
+            "serde"
+        );
+    }
+}
@@ -19,11 +19,13 @@ use test_utils::IS_RUSTC_TEST_SUITE;
 extern crate clippy_lints;
 extern crate clippy_utils;
 extern crate derive_new;
+extern crate fancy_regex;
 extern crate futures;
 extern crate if_chain;
 extern crate itertools;
 extern crate parking_lot;
 extern crate quote;
+extern crate regex_lite;
 extern crate syn;
 extern crate tokio;
 
@@ -31,19 +33,25 @@ mod test_utils;
 
 /// All crates used in UI tests are listed here
 static TEST_DEPENDENCIES: &[&str] = &[
+    "anyhow",
     "clippy_lints",
     "clippy_utils",
     "derive_new",
+    "fancy_regex",
     "futures",
     "if_chain",
     "itertools",
+    "log",
     "parking_lot",
     "quote",
     "regex",
+    "regex_lite",
     "serde_derive",
     "serde",
     "syn",
+    "thiserror",
     "tokio",
+    "tracing",
 ];
 
 /// Produces a string with an `--extern` flag for all UI test crate
@@ -0,0 +1,18 @@
+//! Declares a plugin like `declare_clippy_plugin!`, but claims to be built against other sources
+//! of `clippy_lints`.
+
+use clippy_lints::plugin::{Declaration, Registry};
+
+#[no_mangle]
+pub static CLIPPY_PLUGIN_API_VERSION: u32 = clippy_lints::plugin::API_VERSION;
+
+#[no_mangle]
+pub static CLIPPY_PLUGIN_DECLARATION: Declaration = Declaration {
+    rustc_commit_hash: clippy_lints::plugin::RUSTC_COMMIT_HASH,
+    clippy_lints_fingerprint: "0000000000000000",
+    register,
+};
+
+fn register(_: &mut Registry<'_>) {
+    panic!("a plugin with a mismatched fingerprint was loaded");
+}
@@ -0,0 +1,34 @@
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_session;
+
+use clippy_lints::plugin::Registry;
+use clippy_utils::diagnostics::span_lint;
+use rustc_hir::{Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+
+declare_tool_lint! {
+    pub clippy::FORBIDDEN_FUNCTION,
+    Warn,
+    "functions named `forbidden`"
+}
+
+declare_lint_pass!(ForbiddenFunction => [FORBIDDEN_FUNCTION]);
+
+impl<'tcx> LateLintPass<'tcx> for ForbiddenFunction {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if matches!(item.kind, ItemKind::Fn(..)) && item.ident.as_str() == "forbidden" {
+            span_lint(cx, FORBIDDEN_FUNCTION, item.span, "function named `forbidden`");
+        }
+    }
+}
+
+clippy_lints::declare_clippy_plugin!(register);
+
+fn register(registry: &mut Registry<'_>) {
+    registry.lint_store.register_lints(&[&FORBIDDEN_FUNCTION]);
+    registry.lint_store.register_late_pass(|_| Box::new(ForbiddenFunction));
+}
@@ -0,0 +1,88 @@
+#![feature(lazy_cell)]
+
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::sync::{Mutex, PoisonError};
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+/// Both tests build and check the same crates
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Builds the plugins of `tests/plugin_test` and runs Clippy on its main crate with `plugin`
+/// loaded.
+fn run_with_plugin(plugin: &str) -> Output {
+    let _guard = LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("plugin_test");
+    let cwd = root.join("tests/plugin_test");
+
+    // Build the plugins with the dependency versions Clippy itself was built with
+    fs::copy(root.join("Cargo.lock"), cwd.join("Cargo.lock")).unwrap();
+    let output = Command::new("cargo")
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .arg("build")
+        .args(["-p", "clippy_test_plugin"])
+        .args(["-p", "clippy_mismatched_test_plugin"])
+        .output()
+        .unwrap();
+    println!("build status: {}", output.status);
+    println!("build stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    // Make sure the main crate is checked again
+    Command::new("cargo")
+        .current_dir(&cwd)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .arg("clean")
+        .args(["-p", "plugin_test"])
+        .output()
+        .unwrap();
+
+    let plugin = target_dir
+        .join("debug")
+        .join(format!("{DLL_PREFIX}{plugin}{DLL_SUFFIX}"));
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .env("CLIPPY_PLUGINS", plugin)
+        .arg("clippy")
+        .args(["-p", "plugin_test"])
+        .output()
+        .unwrap();
+
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+#[test]
+fn test_plugin_lint_is_emitted() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let output = run_with_plugin("clippy_test_plugin");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success());
+    assert!(stderr.contains("warning: function named `forbidden`"));
+    assert!(stderr.contains("`#[warn(clippy::forbidden_function)]` on by default"));
+}
+
+#[test]
+fn test_plugin_with_mismatched_fingerprint_is_rejected() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let output = run_with_plugin("clippy_mismatched_test_plugin");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("could not load Clippy plugin"));
+    assert!(stderr.contains("it was built against `clippy_lints` with fingerprint 0000000000000000"));
+}
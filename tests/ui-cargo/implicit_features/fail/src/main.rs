@@ -0,0 +1,3 @@
+#![warn(clippy::implicit_features)]
+
+fn main() {}
@@ -0,0 +1,5 @@
+#![no_std]
+#![warn(clippy::std_in_default_features)]
+
+#[cfg(feature = "std")]
+extern crate std;
@@ -0,0 +1,2 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(clippy::std_in_default_features)]
@@ -0,0 +1,18 @@
+#![warn(clippy::undeclared_features)]
+#![allow(dead_code)]
+
+#[cfg(feature = "serde")]
+fn serialize() {}
+
+#[cfg(feature = "sedre")]
+fn deserialize() {}
+
+#[cfg(all(unix, not(feature = "loging")))]
+fn log() {}
+
+#[cfg_attr(feature = "unknown", derive(Debug))]
+struct S;
+
+fn main() {
+    if cfg!(feature = "tracing") {}
+}
@@ -0,0 +1 @@
+pub fn cfg_macro() {}
@@ -0,0 +1,8 @@
+#[macro_export]
+macro_rules! used {
+    () => {
+        ()
+    };
+}
+
+pub fn used() {}
@@ -0,0 +1,6 @@
+#![warn(clippy::unused_dependencies)]
+
+fn main() {
+    dep_used::used!();
+    dep_optional::optional();
+}
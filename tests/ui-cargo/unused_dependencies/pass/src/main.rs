@@ -0,0 +1,12 @@
+#![warn(clippy::unused_dependencies)]
+
+fn main() {
+    renamed::used();
+    #[cfg(feature = "optional")]
+    dep_optional::optional();
+    if cfg!(feature = "cfg_macro") {
+        dep_cfg_macro::cfg_macro();
+    }
+    #[cfg(any())]
+    dep_target::target();
+}
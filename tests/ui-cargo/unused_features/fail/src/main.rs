@@ -0,0 +1,7 @@
+#![warn(clippy::unused_features)]
+#![cfg_attr(feature = "in_crate_attr", allow(dead_code))]
+
+#[cfg(feature = "used")]
+fn f() {}
+
+fn main() {}
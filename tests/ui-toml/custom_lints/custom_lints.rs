@@ -0,0 +1,46 @@
+#![warn(clippy::literal_get)]
+
+mod db {
+    pub struct Row;
+
+    impl Row {
+        pub fn unwrap(&self) -> u32 {
+            0
+        }
+
+        pub fn get(&self, index: usize) -> u32 {
+            index as u32
+        }
+    }
+}
+
+use db::Row;
+use std::thread;
+use std::time::Duration;
+
+fn rows(row: &Row, index: usize, secs: u64) {
+    row.unwrap();
+    //~^ ERROR: called `unwrap` on a database row
+    index.checked_add(1).unwrap();
+
+    row.get(0);
+    //~^ ERROR: column accessed by a literal index
+    Row::get(row, 1);
+    //~^ ERROR: column accessed by a literal index
+    row.get(index);
+
+    thread::sleep(Duration::from_secs(1));
+    //~^ ERROR: called `std::thread::sleep`
+    //~| ERROR: duration created from a literal
+    let duration = Duration::from_secs(secs);
+    std::thread::sleep(duration);
+    //~^ ERROR: called `std::thread::sleep`
+}
+
+#[allow(clippy::row_unwrap, clippy::thread_sleep, clippy::literal_duration)]
+fn allowed(row: &Row) {
+    row.unwrap();
+    thread::sleep(Duration::from_secs(1));
+}
+
+fn main() {}
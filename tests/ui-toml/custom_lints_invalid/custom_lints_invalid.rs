@@ -0,0 +1,3 @@
+//@error-in-other-file: invalid custom lint
+
+fn main() {}
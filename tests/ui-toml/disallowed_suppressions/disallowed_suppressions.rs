@@ -0,0 +1,36 @@
+#![feature(lint_reasons)]
+#![warn(clippy::disallowed_suppressions)]
+#![allow(clippy::needless_return)]
+
+#[allow(clippy::unwrap_used)]
+fn unwrap() {}
+
+#[expect(dead_code)]
+fn dead() {}
+
+#[allow(clippy::restriction)]
+fn group() {}
+
+#[allow(unused)]
+fn rustc_group() {}
+
+#[allow(clippy::indexing_slicing)]
+fn without_reason() {}
+
+#[allow(clippy::indexing_slicing, reason = "callers check the length")]
+fn non_matching_reason() {}
+
+#[allow(clippy::indexing_slicing, reason = "TICKET-1234: callers check the length")]
+fn matching_reason() {}
+
+// The lint is emitted at the level of the crate root
+#[allow(clippy::disallowed_suppressions, clippy::unwrap_used)]
+fn allowed() {}
+
+#[warn(clippy::unwrap_used)]
+#[deny(dead_code)]
+fn not_suppressed() {}
+
+fn main() {
+    not_suppressed();
+}
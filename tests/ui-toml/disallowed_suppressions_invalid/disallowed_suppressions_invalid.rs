@@ -0,0 +1,4 @@
+//@error-in-other-file: unknown lint
+//@error-in-other-file: invalid `reason` pattern
+
+fn main() {}
@@ -0,0 +1,23 @@
+#![warn(clippy::uninlined_format_args)]
+
+extern crate log;
+
+pub fn log_impl(args: std::fmt::Arguments<'_>) {
+    eprintln!("[my_log] {args}");
+}
+
+#[macro_export]
+macro_rules! my_log {
+    ($($arg:tt)*) => {
+        $crate::log_impl(format_args!($($arg)*))
+    };
+}
+
+fn main() {
+    let x = 1;
+    my_log!("{}", x);
+    //~^ ERROR: variables can be used directly in the `format!` string
+
+    // the macros of `log` are only checked when `..` is part of the list
+    log::info!("{}", x);
+}
@@ -0,0 +1,40 @@
+#![warn(clippy::uninlined_format_args, clippy::to_string_in_format_args)]
+
+extern crate anyhow;
+extern crate log;
+extern crate thiserror;
+extern crate tracing;
+
+#[derive(Debug, thiserror::Error)]
+#[error("limit: {}", i32::MAX.to_string())]
+//~^ ERROR: `to_string` applied to a type that implements `Display` in `write!` args
+struct Limit;
+
+#[derive(Debug, thiserror::Error)]
+#[error("{name} {}", .code)]
+struct Named {
+    name: String,
+    code: u32,
+}
+
+fn main() {
+    let x = 1;
+    let id = "id";
+
+    log::info!("{}", x);
+    //~^ ERROR: variables can be used directly in the `format!` string
+    log::warn!(target: "t", "{}", id.len().to_string());
+    //~^ ERROR: `to_string` applied to a type that implements `Display` in `warn!` args
+    log::info!("{x}");
+
+    tracing::info!("{}", x);
+    //~^ ERROR: variables can be used directly in the `format!` string
+    tracing::info!(user = %id, "msg {}", x);
+    //~^ ERROR: variables can be used directly in the `format!` string
+    tracing::info!(user = %id, x, "msg");
+
+    let _ = anyhow::anyhow!("{}", x);
+    //~^ ERROR: variables can be used directly in the `format!` string
+    let _ = || -> anyhow::Result<()> { anyhow::bail!("{}", id.len().to_string()) };
+    //~^ ERROR: `to_string` applied to a type that implements `Display` in `bail!` args
+}
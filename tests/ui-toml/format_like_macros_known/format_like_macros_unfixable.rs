@@ -0,0 +1,23 @@
+//@no-rustfix
+#![warn(clippy::format_in_format_args)]
+
+extern crate log;
+extern crate thiserror;
+extern crate tracing;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("{}", format!("io {}", .0))]
+    //~^ ERROR: `format!` in `write!` args
+    Io(String),
+}
+
+fn main() {
+    let x = 1;
+    let id = "id";
+
+    log::error!("{}", format!("{x}"));
+    //~^ ERROR: `format!` in `error!` args
+    tracing::error!(user = %id, "msg {}", format!("{x}"));
+    //~^ ERROR: `format!` in `error!` args
+}
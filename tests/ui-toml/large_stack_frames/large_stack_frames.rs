@@ -0,0 +1,43 @@
+#![warn(clippy::large_stack_frames)]
+
+use std::hint::black_box;
+
+#[inline(never)]
+fn leaf() {
+    let buf = [0u8; 600];
+    black_box(&buf);
+}
+
+#[inline(never)]
+fn middle() {
+    let buf = [0u8; 300];
+    black_box(&buf);
+    leaf();
+}
+
+fn entry() {
+    //~^ ERROR: this function allocates a large amount of stack space
+    let buf = [0u8; 200];
+    black_box(&buf);
+    middle();
+}
+
+#[inline(never)]
+fn recursive(n: u32) {
+    let buf = [0u8; 100];
+    black_box(&buf);
+    if n > 0 {
+        recursive(n - 1);
+    }
+}
+
+fn small_callees() {
+    recursive(3);
+    leaf();
+}
+
+fn main() {
+    //~^ ERROR: this function allocates a large amount of stack space
+    entry();
+    small_callees();
+}
@@ -0,0 +1,3 @@
+//@error-in-other-file: could not load Clippy plugin
+
+fn main() {}
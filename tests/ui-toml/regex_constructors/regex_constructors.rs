@@ -0,0 +1,33 @@
+#![warn(clippy::invalid_regex, clippy::trivial_regex, clippy::regex_creation_in_loops)]
+
+extern crate regex;
+
+use regex::Regex;
+
+fn cached_regex(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap()
+}
+
+mod util {
+    pub fn compile(pattern: &str, case_insensitive: bool) -> regex::Regex {
+        regex::RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+            .unwrap()
+    }
+}
+
+fn main() {
+    let _ = cached_regex("[z-a]");
+    //~^ ERROR: regex syntax error: invalid character class range, the start must be <= th
+    let _ = util::compile("(?ixi)", true);
+    //~^ ERROR: regex syntax error: duplicate flag
+    let _ = cached_regex("foobar");
+    //~^ ERROR: trivial regex
+    let _ = cached_regex("[a-z]+");
+
+    for line in ["a", "b"] {
+        let _ = cached_regex("[a-z]+").is_match(line);
+        //~^ ERROR: compiling a regex in a loop
+    }
+}
@@ -0,0 +1,48 @@
+#![warn(clippy::allocation_in_format_impl)]
+#![allow(clippy::to_string_in_format_args)]
+
+use std::fmt::{self, Debug, Display, Formatter, Write};
+
+struct Point(i32, i32);
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("({}, {})", self.0, self.1))
+        //~^ ERROR: allocating a `String` with `format!` in `Display` impl
+        //~| NOTE: `-D clippy::allocation-in-format-impl` implied by `-D warnings`
+    }
+}
+
+impl Debug for Point {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Point")?;
+        formatter.write_str(&self.0.to_string())?;
+        //~^ ERROR: allocating a `String` with `to_string` in `Debug` impl
+        formatter.write_char(',')?;
+        formatter.write_str(&format!("{}", self.1))
+        //~^ ERROR: allocating a `String` with `format!` in `Debug` impl
+    }
+}
+
+struct Padded(String);
+
+impl Display for Padded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // padding needs the whole string
+        f.pad(&format!("<{}>", self.0))?;
+        f.write_str(&self.0)?;
+        f.write_str(self.0.as_str())?;
+        for c in self.0.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+// not a formatting trait impl
+fn describe(point: &Point, out: &mut String) -> fmt::Result {
+    let _: String = ["a", "b"].into_iter().collect();
+    out.write_str(&format!("{point}"))
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+//@no-rustfix
+#![warn(clippy::allocation_in_format_impl)]
+
+use std::fmt::{self, Display, Formatter};
+
+struct Path(Vec<(i32, i32)>);
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let points: String = self.0.iter().map(|(x, y)| x.to_string() + &y.to_string()).collect();
+        //~^ ERROR: collecting into a `String` in `Display` impl
+        write!(f, "[{points}]")
+    }
+}
+
+struct Words(Vec<char>);
+
+impl Display for Words {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.iter().collect::<String>())
+        //~^ ERROR: collecting into a `String` in `Display` impl
+    }
+}
+
+struct Id(u32);
+
+// `ToString` without `Display`, so `write!(f, "{}", ..)` doesn't compile
+impl ToString for Id {
+    fn to_string(&self) -> String {
+        format!("#{}", self.0)
+    }
+}
+
+struct Tagged(Id);
+
+impl fmt::Debug for Tagged {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_string())
+        //~^ ERROR: allocating a `String` with `to_string` in `Debug` impl
+    }
+}
+
+fn main() {}
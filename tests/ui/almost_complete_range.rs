@@ -7,6 +7,7 @@
 #![allow(ellipsis_inclusive_range_patterns)]
 #![allow(clippy::needless_parens_on_range_literals)]
 #![allow(clippy::double_parens)]
+#![allow(clippy::incompatible_msrv)]
 
 extern crate proc_macros;
 use proc_macros::{external, inline_macros};
@@ -0,0 +1,6 @@
+fn min_of_first(v: &[u32]) {
+    #[clippy::author = "min_of_first"]
+    let _ = std::cmp::min(v.first().copied(), Some(3));
+}
+
+fn main() {}
@@ -3,6 +3,7 @@
     unused,
     // Int::max_value will be deprecated in the future
     deprecated,
+    clippy::incompatible_msrv,
 )]
 #![warn(clippy::checked_conversions)]
 
@@ -0,0 +1,12 @@
+#![warn(clippy::uninlined_format_args)]
+
+extern crate log;
+
+fn main() {
+    let x = 1;
+    println!("{}", x);
+    //~^ ERROR: variables can be used directly in the `format!` string
+
+    // only the `std` macros are checked by default
+    log::info!("{}", x);
+}
@@ -0,0 +1,57 @@
+#![feature(custom_inner_attributes)]
+#![warn(clippy::incompatible_msrv)]
+#![clippy::msrv = "1.62"]
+
+use std::collections::BinaryHeap;
+use std::num::NonZeroU32;
+
+fn main() {
+    let _ = Some(2).is_some_and(|x| x % 2 == 0);
+    //~^ ERROR: `std::option::Option::<T>::is_some_and` is stable since Rust 1.70.0, but the MSRV
+    let _ = Option::is_some_and(Some(2), |x| x % 2 == 0);
+    //~^ ERROR: `std::option::Option::<T>::is_some_and` is stable since Rust 1.70.0, but the MSRV
+    let _ = Some(2).map_or(false, |x| x % 2 == 0);
+    let _ = std::path::MAIN_SEPARATOR_STR;
+    //~^ ERROR: `std::path::MAIN_SEPARATOR_STR` is stable since Rust 1.68.0, but the MSRV
+    let _ = NonZeroU32::MIN;
+    //~^ ERROR: `std::num::NonZeroU32::MIN` is stable since Rust 1.70.0, but the MSRV
+    let mut heap = BinaryHeap::from([1, 2, 3]);
+    heap.retain(|&x| x > 1);
+    //~^ ERROR: `std::collections::BinaryHeap::<T, A>::retain` is stable since Rust 1.70.0, but the MSRV
+    let _ = std::f64::consts::TAU;
+}
+
+#[clippy::msrv = "1.70"]
+fn newer() {
+    let _ = Some(2).is_some_and(|x| x % 2 == 0);
+    let _ = std::path::MAIN_SEPARATOR_STR;
+}
+
+macro_rules! local {
+    () => {
+        Some(2).is_some_and(|x| x % 2 == 0)
+    };
+}
+
+fn in_macro() {
+    let _ = local!();
+    //~^ ERROR: `std::option::Option::<T>::is_some_and` is stable since Rust 1.70.0, but the MSRV
+}
+
+#[test]
+fn test() {
+    let _ = Some(2).is_some_and(|x| x % 2 == 0);
+    //~^ ERROR: `std::option::Option::<T>::is_some_and` is stable since Rust 1.70.0, but the MSRV
+}
+
+async fn ready() {}
+
+// The paths created by desugaring aren't linted
+async fn desugared(v: Vec<u32>) -> Option<u32> {
+    ready().await;
+    for x in v {
+        let _ = x;
+    }
+    let x = Some(1)?;
+    Some(x)
+}
@@ -0,0 +1,62 @@
+#![warn(clippy::inconsistent_lock_order)]
+#![allow(clippy::significant_drop_tightening)]
+
+use std::sync::{Mutex, RwLock};
+
+static GLOBAL: Mutex<u32> = Mutex::new(0);
+
+struct Bank {
+    accounts: Mutex<Vec<u64>>,
+    log: RwLock<Vec<String>>,
+    stats: Mutex<u32>,
+    extra: Mutex<u32>,
+}
+
+impl Bank {
+    fn deposit(&self, amount: u64) {
+        let mut accounts = self.accounts.lock().unwrap();
+        let mut log = self.log.write().unwrap();
+        accounts[0] += amount;
+        log.push(format!("deposit {amount}"));
+    }
+
+    fn audit(&self) {
+        let log = self.log.read().unwrap();
+        let accounts = self.accounts.lock().unwrap();
+        //~^ ERROR: `Bank::accounts` is locked while holding `Bank::log`
+        println!("{log:?} {accounts:?}");
+    }
+
+    fn global_then_stats(&self) {
+        let global = GLOBAL.lock().unwrap();
+        *self.stats.lock().unwrap() += *global;
+    }
+
+    fn stats_then_global(&self) -> Result<(), Box<dyn std::error::Error + '_>> {
+        let stats = self.stats.lock()?;
+        if let Ok(mut global) = GLOBAL.lock() {
+            //~^ ERROR: `GLOBAL` is locked while holding `Bank::stats`
+            *global += *stats;
+        }
+        Ok(())
+    }
+
+    // Not nested, the first guard is dropped before the second lock is acquired.
+    fn extra_then_stats(&self) {
+        let extra = self.extra.lock().unwrap();
+        let value = *extra;
+        drop(extra);
+        *self.stats.lock().unwrap() += value;
+    }
+
+    fn stats_then_extra(&self) {
+        let value = *self.stats.lock().unwrap();
+        {
+            let stats = self.stats.lock().unwrap();
+            println!("{stats}");
+        }
+        *self.extra.lock().unwrap() += value;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,88 @@
+#![warn(clippy::missing_send_sync_bounds)]
+#![allow(clippy::non_send_fields_in_send_ty)]
+
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+struct MyBox<T> {
+    ptr: *mut T,
+}
+
+unsafe impl<T> Send for MyBox<T> {}
+//~^ ERROR: `unsafe impl Send` for `MyBox<T>` does not require `T: Send`
+unsafe impl<T> Sync for MyBox<T> {}
+//~^ ERROR: `unsafe impl Sync` for `MyBox<T>` does not require `T: Sync`
+
+struct MyCell<T> {
+    value: UnsafeCell<T>,
+}
+
+// `T: Send` would be enough for a mutex, but the lint can't tell
+unsafe impl<T: Send> Sync for MyCell<T> {}
+//~^ ERROR: `unsafe impl Sync` for `MyCell<T>` does not require `T: Sync`
+
+struct Shared<T> {
+    value: Box<T>,
+}
+
+impl<T> std::ops::Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+// Sharing `Shared<Cell<u32>>` would share the `Cell`
+unsafe impl<T: Send> Sync for Shared<T> {}
+//~^ ERROR: `unsafe impl Sync` for `Shared<T>` does not require `T: Sync`
+
+struct Pair<A, B> {
+    first: Option<A>,
+    second: Vec<NonNull<B>>,
+}
+
+unsafe impl<A, B> Send for Pair<A, B> {}
+//~^ ERROR: `unsafe impl Send` for `Pair<A, B>` does not require `A: Send, B: Send`
+
+unsafe impl<A: Sync, B> Sync for Pair<A, B> where B: Sync {}
+
+struct Owned<T> {
+    ptr: NonNull<u8>,
+    marker: PhantomData<T>,
+}
+
+unsafe impl<T> Send for Owned<T> {}
+//~^ ERROR: `unsafe impl Send` for `Owned<T>` does not require `T: Send`
+
+// Raw pointers and function pointers in `PhantomData` are only markers
+struct Id<T> {
+    id: usize,
+    marker: PhantomData<*const T>,
+}
+
+unsafe impl<T> Send for Id<T> {}
+unsafe impl<T> Sync for Id<T> {}
+
+struct Factory<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+unsafe impl<T> Send for Factory<T> {}
+
+// Only type parameters which are stored in the type are relevant
+struct Tagged<T, U> {
+    value: *mut T,
+    tag: PhantomData<fn(U)>,
+}
+
+unsafe impl<T: Send, U> Send for Tagged<T, U> {}
+
+struct Concrete {
+    ptr: *mut u8,
+}
+
+unsafe impl Send for Concrete {}
+
+fn main() {}
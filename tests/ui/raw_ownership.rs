@@ -0,0 +1,116 @@
+#![warn(clippy::double_from_raw, clippy::drop_after_ptr_read)]
+#![allow(unused, clippy::box_collection, clippy::if_same_then_else)]
+
+use std::mem::{self, ManuallyDrop};
+use std::ptr;
+
+struct RawBuf {
+    ptr: *mut u8,
+    len: usize,
+    cap: usize,
+}
+
+impl RawBuf {
+    unsafe fn to_vec(&self) -> Vec<u8> {
+        Vec::from_raw_parts(self.ptr, self.len, self.cap)
+    }
+
+    unsafe fn two_vecs(&self) -> (Vec<u8>, Vec<u8>) {
+        let a = Vec::from_raw_parts(self.ptr, self.len, self.cap);
+        let b = Vec::from_raw_parts(self.ptr, self.len, self.cap);
+        //~^ ERROR: `Vec::from_raw_parts` takes ownership of a pointer which is already owned
+        (a, b)
+    }
+}
+
+unsafe fn from_raw_twice(p: *mut String) {
+    let a = Box::from_raw(p);
+    let b = Box::from_raw(p);
+    //~^ ERROR: `Box::from_raw` takes ownership of a pointer which is already owned
+}
+
+unsafe fn from_raw_after_drop(p: *mut String) {
+    drop(Box::from_raw(p));
+    let _ = Box::from_raw(p);
+    //~^ ERROR: `Box::from_raw` takes ownership of a pointer which is already owned
+}
+
+unsafe fn from_raw_branches(p: *mut String, c: bool) -> Box<String> {
+    if c { Box::from_raw(p) } else { Box::from_raw(p) }
+}
+
+unsafe fn from_raw_round_trip(p: *mut String) {
+    let a = Box::from_raw(p);
+    let p = Box::into_raw(a);
+    let b = Box::from_raw(p);
+}
+
+unsafe fn from_raw_leaked(p: *mut String) {
+    let a = Box::from_raw(p);
+    mem::forget(a);
+    let b = Box::from_raw(p);
+}
+
+unsafe fn from_raw_reassigned(mut p: *mut String, q: *mut String) {
+    let a = Box::from_raw(p);
+    p = q;
+    let b = Box::from_raw(p);
+}
+
+// The reference count may have been incremented
+unsafe fn rc_from_raw_twice(p: *const String) {
+    let a = std::rc::Rc::from_raw(p);
+    let b = std::rc::Rc::from_raw(p);
+}
+
+// Linted by `from_raw_with_void_ptr`
+unsafe fn void_from_raw_twice(p: *mut std::ffi::c_void) {
+    let a = Box::from_raw(p);
+    //~^ ERROR: creating a `Box` from a void raw pointer
+    let b = Box::from_raw(p as *mut u32);
+}
+
+unsafe fn read_then_scope_end(v: Vec<u8>) -> Vec<u8> {
+    ptr::read(&v)
+    //~^ ERROR: this duplicates a value which is dropped again later
+}
+
+unsafe fn read_then_drop(v: Vec<u8>) {
+    let copy = ptr::read(&v as *const Vec<u8>);
+    //~^ ERROR: this duplicates a value which is dropped again later
+    drop(v);
+    drop(copy);
+}
+
+unsafe fn read_method(s: String) -> String {
+    let p: *const String = &s;
+    p.read()
+    //~^ ERROR: this duplicates a value which is dropped again later
+}
+
+unsafe fn read_then_forget(v: Vec<u8>) -> Vec<u8> {
+    let copy = ptr::read(&v);
+    mem::forget(v);
+    copy
+}
+
+unsafe fn read_copy(x: u32) -> u32 {
+    ptr::read(&x)
+}
+
+unsafe fn read_manually_drop(v: ManuallyDrop<Vec<u8>>) -> ManuallyDrop<Vec<u8>> {
+    ptr::read(&v)
+}
+
+unsafe fn read_then_write(mut v: Vec<u8>) {
+    let old = ptr::read(&v);
+    ptr::write(&mut v, Vec::new());
+    drop(old);
+}
+
+unsafe fn read_then_leak(v: Vec<u8>) {
+    let copy = ptr::read(&v);
+    mem::forget(copy);
+}
+
+fn main() {}
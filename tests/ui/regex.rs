@@ -5,11 +5,13 @@
     clippy::needless_borrow
 )]
 #![warn(clippy::invalid_regex, clippy::trivial_regex)]
+#![feature(lazy_cell)]
 
 extern crate regex;
 
 use regex::bytes::{Regex as BRegex, RegexBuilder as BRegexBuilder, RegexSet as BRegexSet};
-use regex::{Regex, RegexBuilder, RegexSet};
+use regex::{Regex, RegexBuilder, RegexSet, RegexSetBuilder};
+use std::sync::{LazyLock, OnceLock};
 
 const OPENING_PAREN: &str = "(";
 const NOT_A_REAL_REGEX: &str = "foobar";
@@ -60,6 +62,28 @@ fn syntax_error() {
     let invalid_utf8_should_not_lint = BRegex::new("(?-u).");
     let invalid_utf8_should_lint = Regex::new("(?-u).");
     //~^ ERROR: regex syntax error: pattern can match invalid UTF-8
+
+    let set_builder_error = RegexSetBuilder::new(&["[a-z]+", "(?ixi)"]);
+    //~^ ERROR: regex syntax error: duplicate flag
+    let vec_set_error = RegexSet::new(vec!["[a-z]+", "[z-a]"]);
+    //~^ ERROR: regex syntax error: invalid character class range, the start must be <= th
+    let array_set_error = RegexSet::new(["[a-z]+", "(?-u)."]);
+    //~^ ERROR: regex syntax error: pattern can match invalid UTF-8
+
+    // each pattern of a set has its own flags
+    let bset_mixed_flags = BRegexSet::new([r"(?-u)[\x80-\xFF]+", r"(?u)\w+", "(?i)[a-z]+"]);
+    let bset_mixed_flags_error = BRegexSet::new([r"(?-u)[\x80-\xFF]+", r"(?u)\w+", "(?i"]);
+    //~^ ERROR: regex syntax error: expected flag but got end of regex
+}
+
+static LAZY_ERROR: LazyLock<Regex> = LazyLock::new(|| Regex::new("[z-a]").unwrap());
+//~^ ERROR: regex syntax error: invalid character class range, the start must be <= th
+
+static ONCE: OnceLock<Regex> = OnceLock::new();
+
+fn once_error() -> &'static Regex {
+    ONCE.get_or_init(|| Regex::new("(?ixi)").unwrap())
+    //~^ ERROR: regex syntax error: duplicate flag
 }
 
 fn trivial_regex() {
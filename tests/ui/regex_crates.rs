@@ -0,0 +1,51 @@
+#![allow(unused)]
+#![warn(clippy::invalid_regex, clippy::trivial_regex)]
+
+extern crate fancy_regex;
+extern crate regex;
+extern crate regex_lite;
+
+fn regex_lite() {
+    let error = regex_lite::Regex::new("[z-a]");
+    //~^ ERROR: regex syntax error: invalid character class range, the start must be <= th
+    //~| NOTE: `-D clippy::invalid-regex` implied by `-D warnings`
+    let builder_error = regex_lite::RegexBuilder::new("(?ixi)");
+    //~^ ERROR: regex syntax error: duplicate flag
+    let trivial = regex_lite::Regex::new("foobar");
+    //~^ ERROR: trivial regex
+    let valid = regex_lite::Regex::new("[a-z]+");
+}
+
+fn fancy_regex() {
+    let error = fancy_regex::Regex::new("[z-a]");
+    //~^ ERROR: regex syntax error: invalid character class range, the start must be <= th
+    let builder_error = fancy_regex::RegexBuilder::new("(?ixi)");
+    //~^ ERROR: regex syntax error: duplicate flag
+    let trivial = fancy_regex::Regex::new("foobar");
+    //~^ ERROR: trivial regex
+
+    // syntax added by `fancy-regex`
+    let backreference = fancy_regex::Regex::new(r"(\w+) \1");
+    let named_backreference = fancy_regex::Regex::new(r"(?<word>\w+) \k<word>");
+    let look_ahead = fancy_regex::Regex::new(r"\w+(?=!)");
+    let look_behind = fancy_regex::Regex::new(r"(?<!-)\d+");
+    let atomic_group = fancy_regex::Regex::new(r"(?>a+)b");
+    let conditional = fancy_regex::Regex::new(r"(<)?\w+(?(1)>)");
+    let keep_out = fancy_regex::Regex::new(r"foo\Kbar");
+    let contiguous = fancy_regex::Regex::new(r"\Gfoo");
+
+    // not accepted by `fancy-regex` either
+    let unknown_escape = fancy_regex::Regex::new(r"foo\q");
+    //~^ ERROR: regex syntax error: unrecognized escape sequence
+    let unknown_flag = fancy_regex::Regex::new(r"(?z)foo");
+    //~^ ERROR: regex syntax error: unrecognized flag
+}
+
+fn set_builders() {
+    let set_builder_error = regex::RegexSetBuilder::new(["[a-z]+", "[z-a]"]);
+    //~^ ERROR: regex syntax error: invalid character class range, the start must be <= th
+    let bytes_set_builder_error = regex::bytes::RegexSetBuilder::new([r"(?-u)[\x80-\xFF]+", "(?i"]);
+    //~^ ERROR: regex syntax error: expected flag but got end of regex
+}
+
+fn main() {}
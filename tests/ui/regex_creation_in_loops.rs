@@ -0,0 +1,79 @@
+#![warn(clippy::regex_creation_in_loops)]
+#![allow(clippy::needless_range_loop)]
+
+extern crate regex;
+
+use regex::bytes::Regex as BRegex;
+use regex::{Regex, RegexSet};
+use std::sync::OnceLock;
+
+const PATTERN: &str = "^[0-9]+$";
+
+fn in_loops(lines: &[&str]) {
+    for line in lines {
+        let re = Regex::new("^[0-9]+$").unwrap();
+        //~^ ERROR: compiling a regex in a loop
+        let _ = re.is_match(line);
+    }
+
+    let mut i = 0;
+    while i < lines.len() {
+        let re = BRegex::new(PATTERN).unwrap();
+        //~^ ERROR: compiling a regex in a loop
+        let _ = re.is_match(lines[i].as_bytes());
+        i += 1;
+    }
+
+    loop {
+        let set = RegexSet::new(["a+", "b+"]).unwrap();
+        //~^ ERROR: compiling a regex in a loop
+        if set.is_match(lines[0]) {
+            break;
+        }
+    }
+
+    for _ in 0..2 {
+        for line in lines {
+            // only linted in the inner loop
+            let _ = Regex::new(PATTERN).unwrap().is_match(line);
+            //~^ ERROR: compiling a regex in a loop
+        }
+    }
+}
+
+fn in_iterator_closures(lines: &[&str]) {
+    let _ = lines
+        .iter()
+        .filter(|line| Regex::new(PATTERN).unwrap().is_match(line))
+        //~^ ERROR: compiling a regex in a closure called for every item
+        .count();
+    lines.iter().for_each(|line| {
+        let _ = Regex::new(PATTERN).unwrap().is_match(line);
+        //~^ ERROR: compiling a regex in a closure called for every item
+    });
+}
+
+fn no_lint(lines: &[&str], patterns: &[&str]) {
+    // compiled once
+    let re = Regex::new(PATTERN).unwrap();
+    for line in lines {
+        let _ = re.is_match(line);
+    }
+
+    // the pattern changes between iterations
+    for pattern in patterns {
+        let _ = Regex::new(pattern).unwrap().is_match(lines[0]);
+    }
+    let _ = patterns.iter().all(|pattern| Regex::new(pattern).is_ok());
+
+    // cached
+    static RE: OnceLock<Regex> = OnceLock::new();
+    for line in lines {
+        let _ = RE.get_or_init(|| Regex::new(PATTERN).unwrap()).is_match(line);
+    }
+
+    // not an iterator
+    let _ = Some(lines[0]).map(|line| Regex::new(PATTERN).unwrap().is_match(line));
+}
+
+fn main() {}
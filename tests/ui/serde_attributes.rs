@@ -0,0 +1,140 @@
+#![warn(
+    clippy::serde_rename_conflict,
+    clippy::serde_deny_unknown_fields_with_flatten,
+    clippy::serde_skip_serializing_without_default,
+    clippy::serde_untagged_overlap
+)]
+#![allow(dead_code)]
+
+extern crate serde;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RenameAll {
+    user_id: u32,
+    #[serde(rename = "userId")]
+    legacy_id: u32,
+    //~^ ERROR: field `legacy_id` is serialized as `userId`, like field `user_id`
+    #[serde(skip)]
+    skipped: u32,
+}
+
+#[derive(Deserialize)]
+struct Renamed {
+    a: u32,
+    #[serde(rename = "a")]
+    b: u32,
+    //~^ ERROR: field `b` is serialized as `a`, like field `a`
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Variants {
+    HttpRequest,
+    #[serde(rename = "http_request")]
+    Legacy,
+    //~^ ERROR: variant `Legacy` is serialized as `http_request`, like variant `HttpRequest`
+    #[serde(rename_all = "kebab-case")]
+    Struct {
+        field_name: u32,
+        #[serde(rename = "field-name")]
+        other: u32,
+        //~^ ERROR: field `other` is serialized as `field-name`, like field `field_name`
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all_fields = "UPPERCASE")]
+enum AllFields {
+    A {
+        x: u32,
+        #[serde(rename = "X")]
+        y: u32,
+        //~^ ERROR: field `y` is serialized as `X`, like field `x`
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Strict {
+    name: String,
+    #[serde(flatten)]
+    //~^ ERROR: `#[serde(flatten)]` is not supported with `#[serde(deny_unknown_fields)]`
+    extra: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Inner {
+    a: u32,
+}
+
+#[derive(Deserialize)]
+struct Outer {
+    b: u32,
+    #[serde(flatten)]
+    //~^ ERROR: `#[serde(flatten)]` is not supported on a type with `#[serde(deny_unknown_fields)]`
+    inner: Inner,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Skipped {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    //~^ ERROR: this field is skipped when serializing, but required when deserializing
+    items: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    defaulted: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    optional: Option<u32>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ContainerDefault {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    items: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SerializeOnly {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    items: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Circle { radius: f64 },
+    Ring { radius: f64, width: f64 },
+    //~^ ERROR: variant `Ring` of this untagged enum is never deserialized, as `Circle` accepts the same input
+    Int(i64),
+    Other(i64),
+    //~^ ERROR: variant `Other` of this untagged enum is never deserialized, as `Int` accepts the same input
+    Pair(i64, String),
+    Name(String),
+    Nothing,
+    Empty,
+    //~^ ERROR: variant `Empty` of this untagged enum is never deserialized, as `Nothing` accepts the same input
+}
+
+#[derive(Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum UntaggedStrict {
+    Circle { radius: f64 },
+    Ring { radius: f64, width: f64 },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UntaggedOrdered {
+    Ring { radius: f64, width: f64 },
+    Circle { radius: f64 },
+    Square { side: f64 },
+    Float(f64),
+    Int(i64),
+}
+
+fn main() {}
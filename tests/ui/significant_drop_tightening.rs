@@ -134,6 +134,21 @@ pub fn unnecessary_contention_with_single_owned_results() {
     }
 }
 
+pub fn unnecessary_contention_with_deref_result() {
+    let mutex = Mutex::new(1i32);
+    let lock = mutex.lock().unwrap();
+    let rslt = *lock;
+    do_heavy_computation_that_takes_time(rslt);
+}
+
+pub fn unnecessary_contention_without_results() {
+    let mutex = Mutex::new(vec![1i32]);
+    let mut lock = mutex.lock().unwrap();
+    lock.push(2);
+    lock.push(3);
+    do_heavy_computation_that_takes_time(());
+}
+
 // Marker used for illustration purposes.
 pub fn do_heavy_computation_that_takes_time<T>(_: T) {}
 
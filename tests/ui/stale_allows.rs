@@ -0,0 +1,101 @@
+//@aux-build:proc_macros.rs
+#![feature(lint_reasons)]
+#![warn(clippy::stale_allows, clippy::undocumented_unsafe_blocks)]
+#![allow(unused)]
+
+extern crate proc_macros;
+use proc_macros::{external, with_span};
+
+#[allow(clippy::needless_return)]
+fn used() -> u32 {
+    return 1;
+}
+
+#[allow(clippy::needless_return)]
+//~^ ERROR: this `allow` of `clippy::needless_return` didn't suppress any lint
+fn stale() -> u32 {
+    1
+}
+
+#[allow(clippy::needless_return, clippy::let_and_return)]
+//~^ ERROR: this `allow` of `clippy::needless_return` didn't suppress any lint
+fn partially_stale() -> u32 {
+    let x = 1;
+    x
+}
+
+#[allow(clippy::let_and_return, clippy::needless_return, clippy::redundant_clone)]
+//~^ ERROR: this `allow` of `clippy::needless_return`, `clippy::redundant_clone` didn't suppress
+fn stale_in_the_middle() -> u32 {
+    let x = 1;
+    x
+}
+
+#[allow(dead_code, clippy::needless_return, reason = "it's a test")]
+//~^ ERROR: this `allow` of `clippy::needless_return` didn't suppress any lint
+fn stale_with_rustc_lint() -> u32 {
+    1
+}
+
+#[allow(clippy::style)]
+fn used_group() -> u32 {
+    return 1;
+}
+
+#[expect(clippy::needless_return)]
+//~^ ERROR: this `expect` of `clippy::needless_return` didn't suppress any lint
+//~| ERROR: this lint expectation is unfulfilled
+fn stale_expect() -> u32 {
+    1
+}
+
+#[allow(clippy::nonminimal_bool)]
+fn used_nonminimal_bool(a: u32, b: u32) -> bool {
+    !(a == b)
+}
+
+#[allow(clippy::nonminimal_bool)]
+//~^ ERROR: this `allow` of `clippy::nonminimal_bool` didn't suppress any lint
+fn stale_nonminimal_bool(a: u32, b: u32) -> bool {
+    a != b
+}
+
+// `undocumented_unsafe_blocks` is skipped when it's allowed
+#[allow(clippy::undocumented_unsafe_blocks)]
+fn used_skipped_lint(p: *const u8) -> u8 {
+    unsafe { *p }
+}
+
+#[allow(clippy::undocumented_unsafe_blocks)]
+//~^ ERROR: this `allow` of `clippy::undocumented_unsafe_blocks` didn't suppress any lint
+fn stale_skipped_lint(p: &u8) -> u8 {
+    *p
+}
+
+#[allow(clippy::stale_allows)]
+mod allowed {
+    #[allow(clippy::needless_return)]
+    fn stale() -> u32 {
+        1
+    }
+}
+
+with_span! {
+    span
+    #[allow(clippy::needless_return)]
+    fn from_macro() -> u32 {
+        1
+    }
+}
+
+external! {
+    #[allow(clippy::needless_return)]
+    fn from_external_macro() -> u32 {
+        1
+    }
+}
+
+fn main() {
+    #[allow(clippy::no_effect)]
+    1;
+}
@@ -0,0 +1,14 @@
+//@no-rustfix
+#![warn(clippy::stale_allows)]
+
+#[cfg_attr(all(), allow(clippy::needless_return))]
+fn stale_cfg_attr() -> u32 {
+    1
+}
+
+#[cfg_attr(all(), allow(clippy::needless_return))]
+fn used_cfg_attr() -> u32 {
+    return 1;
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+//@ignore-32bit
+//@rustc-env:CLIPPY_TYPE_SIZES=-
+#![allow(dead_code, clippy::large_enum_variant)]
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Handle(std::ptr::NonNull<u8>);
+
+enum Message {
+    Quit,
+    Move(Point),
+    Data([u8; 1024]),
+}
+
+enum Small {
+    A(u8),
+    B(u16),
+}
+
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+// Skipped, the layout depends on `T`
+struct Wrapper<T>(T);
+
+mod inner {
+    pub struct Unit;
+}
+
+fn main() {}
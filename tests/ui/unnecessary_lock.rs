@@ -0,0 +1,117 @@
+#![warn(clippy::unnecessary_lock)]
+#![allow(dead_code, clippy::mutex_atomic, clippy::mutex_integer)]
+
+use std::cell::RefCell;
+use std::sync::{mpsc, Arc, Condvar, Mutex, RwLock};
+
+struct Counters {
+    requests: Mutex<usize>,
+    //~^ ERROR: this `Mutex` is only used for single operations on a `usize`
+    errors: Arc<Mutex<u32>>,
+    //~^ ERROR: this `Mutex` is only used for single operations on a `u32`
+    enabled: RwLock<bool>,
+    //~^ ERROR: this `RwLock` is only used for single operations on a `bool`
+}
+
+impl Counters {
+    fn record(&self, failed: bool) {
+        *self.requests.lock().unwrap() += 1;
+        if failed {
+            let mut errors = self.errors.lock().unwrap();
+            *errors += 1;
+        }
+    }
+
+    fn requests(&self) -> usize {
+        *self.requests.lock().unwrap()
+    }
+
+    fn errors(&self) -> u32 {
+        *self.errors.lock().unwrap()
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        *self.enabled.write().unwrap() = enabled;
+    }
+
+    fn is_enabled(&self) -> bool {
+        *self.enabled.read().unwrap()
+    }
+}
+
+struct Settings {
+    name: Mutex<String>,
+    //~^ ERROR: this `Mutex` is only locked to read its value
+    limits: Arc<RwLock<Vec<u32>>>,
+    //~^ ERROR: this `RwLock` is only locked to read its value
+}
+
+impl Settings {
+    fn greeting(&self) -> String {
+        format!("hello, {}", self.name.lock().unwrap())
+    }
+
+    fn name_len(&self) -> usize {
+        self.name.lock().unwrap().len()
+    }
+
+    fn limit(&self, i: usize) -> u32 {
+        let limits = self.limits.read().unwrap();
+        limits[i] + limits.len() as u32
+    }
+}
+
+struct NoLint {
+    // several operations under the same lock
+    total: Mutex<u64>,
+    // modified through a mutable borrow
+    names: Mutex<Vec<String>>,
+    // the lock is shared
+    shared: Arc<Mutex<u32>>,
+    // the guard protects something else
+    critical: Mutex<()>,
+    // the guard is passed to a `Condvar`
+    ready: Mutex<bool>,
+    condvar: Condvar,
+    // the value is not atomic-eligible
+    value: Mutex<u128>,
+    // multiplication has no atomic counterpart
+    product: Mutex<i32>,
+    // the value isn't `Sync`
+    receiver: Arc<Mutex<mpsc::Receiver<u32>>>,
+    // the value is modified through a shared reference
+    cache: Mutex<RefCell<Vec<u32>>>,
+}
+
+impl NoLint {
+    fn update(&self) {
+        let mut total = self.total.lock().unwrap();
+        if *total < 10 {
+            *total += 1;
+        }
+        self.names.lock().unwrap().push(String::new());
+        let _ = Arc::clone(&self.shared);
+        *self.shared.lock().unwrap() += 1;
+        let _guard = self.critical.lock().unwrap();
+        let mut ready = self.ready.lock().unwrap();
+        while !*ready {
+            ready = self.condvar.wait(ready).unwrap();
+        }
+        *self.value.lock().unwrap() += 1;
+        *self.product.lock().unwrap() *= 2;
+        let _ = self.receiver.lock().unwrap().recv();
+        self.cache.lock().unwrap().borrow_mut().push(1);
+    }
+}
+
+pub struct Exported {
+    pub count: Mutex<usize>,
+}
+
+impl Exported {
+    pub fn increment(&self) {
+        *self.count.lock().unwrap() += 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,70 @@
+//@rustc-env:CLIPPY_UNSAFE_INVENTORY=-
+#![allow(
+    clippy::missing_safety_doc,
+    clippy::undocumented_unsafe_blocks,
+    clippy::transmute_ptr_to_ref
+)]
+
+static mut COUNTER: u32 = 0;
+
+extern "C" {
+    fn abs(input: i32) -> i32;
+}
+
+// SAFETY: `Foo` has no fields
+unsafe impl Send for Foo {}
+
+struct Foo;
+
+impl Foo {
+    #[no_mangle]
+    pub extern "C" fn foo_method() {}
+}
+
+#[no_mangle]
+pub extern "C" fn exported() {}
+
+unsafe fn unsafe_function(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+fn main() {
+    let x = 42u32;
+
+    // SAFETY: `x` is a valid `u32` and outlives the call
+    let y = unsafe { unsafe_function(&x) };
+
+    unsafe {
+        COUNTER += y;
+        let _ = abs(-1);
+    }
+
+    let _: u32 = unsafe { std::mem::transmute(1i32) };
+}
+
+impl Foo {
+    fn safe_method() {}
+
+    // SAFETY: `ptr` must point to a valid `u32`
+    unsafe fn unsafe_method(ptr: *const u32) -> u32 {
+        *ptr
+    }
+
+    unsafe fn undocumented_method() {}
+}
+
+trait UnsafeTrait {
+    // SAFETY: callers must hold the lock
+    unsafe fn required();
+
+    unsafe fn undocumented_required();
+
+    // SAFETY: no requirements
+    unsafe fn provided() {}
+}
+
+impl Foo {
+    // SAFETY: takes no arguments and touches no state
+    #[no_mangle]
+    pub extern "C" fn documented_foo_method() {}
+}
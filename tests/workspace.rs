@@ -1,5 +1,6 @@
 #![feature(lazy_cell)]
 
+use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
@@ -145,3 +146,109 @@ fn test_no_deps_ignores_path_deps_in_workspaces() {
     // Make sure Cargo is aware of the new `--cfg` flag.
     lint_path_dep();
 }
+
+#[test]
+fn test_unsafe_inventory_includes_dependencies() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Dependencies checked before without the inventory are not checked again
+    let target_dir = root.join("target").join("unsafe_inventory_test");
+    let inventory_dir = target_dir.join("inventory");
+    let cwd = root.join("tests/workspace_test");
+    let _ = fs::remove_dir_all(&target_dir);
+
+    // `unsafe_dep` is a dependency of `subcrate` outside of the workspace
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .env("CLIPPY_UNSAFE_INVENTORY", &inventory_dir)
+        .arg("clippy")
+        .args(["-p", "subcrate"])
+        .arg("--")
+        .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    let report = fs::read_dir(&inventory_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.file_name().unwrap().to_str().unwrap().starts_with("unsafe_dep-"))
+        .expect("no inventory of `unsafe_dep`");
+    let report = fs::read_to_string(report).unwrap();
+    assert!(report.contains(r#""kind": "unsafe_block""#));
+    assert!(report.contains("SAFETY: `v` is not empty"));
+}
+
+#[test]
+fn test_diagnostics_cache_replays_output() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("diagnostics_cache_test");
+    let cache_dir = target_dir.join("clippy-cache");
+    let cwd = root.join("tests/workspace_test");
+    let _ = fs::remove_dir_all(&target_dir);
+
+    let clippy = |lint_me: bool| {
+        let mut command = Command::new(&*CARGO_CLIPPY_PATH);
+        command
+            .current_dir(&cwd)
+            .env("CARGO_INCREMENTAL", "0")
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .env("CLIPPY_DIAGNOSTICS_CACHE", &cache_dir)
+            .arg("clippy")
+            .args(["-p", "path_dep"])
+            .arg("--")
+            .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+            .arg("--cap-lints=warn");
+        if lint_me {
+            command.args(["--cfg", r#"feature="primary_package_test""#]);
+        }
+        let output = command.output().unwrap();
+        println!("status: {}", output.status);
+        println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+        println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert!(output.status.success());
+
+        // Leave out the progress and timing lines of cargo
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("Checking") && !line.trim_start().starts_with("Finished"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let entries = || {
+        fs::read_dir(&cache_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .map(|path| {
+                let modified = fs::metadata(&path).unwrap().modified().unwrap();
+                (path, modified)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let stored = clippy(true);
+    assert!(stored.contains("empty `loop {}` wastes CPU cycles"));
+    let stored_entries = entries();
+    assert_eq!(stored_entries.len(), 1);
+
+    // Changing the flags makes Cargo run Clippy again when switching back
+    assert!(!clippy(false).contains("empty `loop {}`"));
+    let replayed = clippy(true);
+    assert_eq!(replayed, stored);
+
+    // The entry was used, not written again
+    let (path, modified) = &stored_entries[0];
+    assert!(entries().contains(&(path.clone(), *modified)));
+}
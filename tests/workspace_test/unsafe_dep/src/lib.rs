@@ -0,0 +1,5 @@
+pub fn first(v: &[u8]) -> u8 {
+    assert!(!v.is_empty());
+    // SAFETY: `v` is not empty
+    unsafe { *v.get_unchecked(0) }
+}